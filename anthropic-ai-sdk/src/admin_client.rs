@@ -3,14 +3,38 @@
 //! This module contains the implementations for the Anthropic Admin API endpoints.
 //! It provides functionality for managing API keys and other administrative tasks.
 
-use crate::client::AnthropicClient;
+use crate::client::{AnthropicClient, RequestOptions};
+use crate::pagination::{Cursor, Page, paginate};
 use crate::types::admin::api_keys::{
-    AdminClient, AdminError, AdminUpdateApiKeyParams, ApiKey, ListApiKeysParams,
+    AdminClient, AdminError, AdminUpdateApiKeyParams, ApiKey, ApiKeyStatus, ListApiKeysParams,
     ListApiKeysResponse,
 };
+use crate::types::admin::invites::{
+    CreateInviteBody, Invite, InviteClient, InviteDeleted, ListInvitesParams, ListInvitesResponse,
+};
+use crate::types::admin::reporting::{
+    CostBucket, CostReport, CostReportClient, CostReportParams, UsageBucket, UsageReport,
+    UsageReportClient, UsageReportParams,
+};
+use crate::types::admin::users::{
+    ListUsersParams, ListUsersResponse, OrganizationRole, OrganizationUser, UpdateUserBody,
+    UserClient, UserDeleted,
+};
+use crate::types::admin::workspace_members::{
+    AddWorkspaceMemberBody, ListWorkspaceMembersParams, ListWorkspaceMembersResponse,
+    UpdateWorkspaceMemberBody, WorkspaceMember, WorkspaceMemberClient, WorkspaceMemberDeleted,
+    WorkspaceRole,
+};
+use crate::types::admin::workspaces::{
+    CreateWorkspaceParams, ListWorkspacesParams, ListWorkspacesResponse, UpdateWorkspaceParams,
+    Workspace, WorkspaceClient,
+};
 use async_trait::async_trait;
+use futures_util::Stream;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 
-#[async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl AdminClient for AnthropicClient {
     /// Lists API keys
     ///
@@ -35,7 +59,7 @@ impl AdminClient for AnthropicClient {
     ///
     /// ```no_run
     /// use anthropic_ai_sdk::client::AnthropicClient;
-    /// use anthropic_ai_sdk::types::admin::{AdminClient, AdminError};
+    /// use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError};
     /// use tokio;
     ///
     /// #[tokio::main]
@@ -58,7 +82,8 @@ impl AdminClient for AnthropicClient {
         &'a self,
         params: Option<&'a ListApiKeysParams>,
     ) -> Result<ListApiKeysResponse, AdminError> {
-        self.get("/organizations/api_keys", params).await
+        self.list_api_keys_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Gets a specific API key
@@ -85,7 +110,7 @@ impl AdminClient for AnthropicClient {
     ///
     /// ```no_run
     /// use anthropic_ai_sdk::client::AnthropicClient;
-    /// use anthropic_ai_sdk::types::admin::{AdminClient, AdminError};
+    /// use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError};
     /// use tokio;
     ///
     /// #[tokio::main]
@@ -96,7 +121,7 @@ impl AdminClient for AnthropicClient {
     ///     )?;
     ///
     ///     // Get a specific API key
-    ///     let api_key = client.get_api_key("api_key_xyz").await?;
+    ///     let api_key = AdminClient::get_api_key(&client, "api_key_xyz").await?;
     ///     println!("API Key: {} ({})", api_key.name, api_key.id);
     ///     println!("Status: {:?}", api_key.status);
     ///     println!("Partial Hint: {}", api_key.partial_key_hint);
@@ -105,11 +130,8 @@ impl AdminClient for AnthropicClient {
     /// }
     /// ```
     async fn get_api_key<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKey, AdminError> {
-        self.get(
-            &format!("/organizations/api_keys/{}", api_key_id),
-            Option::<&()>::None,
-        )
-        .await
+        self.get_api_key_with_options(api_key_id, &RequestOptions::default())
+            .await
     }
 
     /// Updates an API key
@@ -138,7 +160,7 @@ impl AdminClient for AnthropicClient {
     ///
     /// ```no_run
     /// use anthropic_ai_sdk::client::AnthropicClient;
-    /// use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError, ApiKeyStatus, UpdateApiKeyParams};
+    /// use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError, ApiKeyStatus, AdminUpdateApiKeyParams};
     /// use tokio;
     ///
     /// #[tokio::main]
@@ -149,7 +171,7 @@ impl AdminClient for AnthropicClient {
     ///     )?;
     ///
     ///     // Update an API key
-    ///     let params = UpdateApiKeyParams::new()
+    ///     let params = AdminUpdateApiKeyParams::new()
     ///         .name("Updated API Key")
     ///         .status(ApiKeyStatus::Inactive);
     ///
@@ -164,10 +186,1311 @@ impl AdminClient for AnthropicClient {
         &'a self,
         api_key_id: &'a str,
         params: &'a AdminUpdateApiKeyParams,
+    ) -> Result<ApiKey, AdminError> {
+        self.update_api_key_with_options(api_key_id, params, &RequestOptions::default())
+            .await
+    }
+
+    /// Archives an API key
+    ///
+    /// Archiving cannot be undone: an archived key can no longer be used or
+    /// reactivated. This is a shorthand for `update_api_key` with
+    /// `ApiKeyStatus::Archived`.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key_id` - The ID of the API key to archive
+    ///
+    /// # Returns
+    ///
+    /// Returns the archived API key on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The API key is not found
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let api_key = client.archive_api_key("api_key_xyz").await?;
+    ///     println!("Archived API Key: {} ({:?})", api_key.id, api_key.status);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn archive_api_key<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKey, AdminError> {
+        let params = AdminUpdateApiKeyParams::new().status(ApiKeyStatus::Archived);
+        self.update_api_key(api_key_id, &params).await
+    }
+
+    async fn list_api_keys_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListApiKeysResponse, AdminError> {
+        self.get("/organizations/api_keys", params, options).await
+    }
+
+    async fn get_api_key_with_options<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<ApiKey, AdminError> {
+        self.get(
+            &format!("/organizations/api_keys/{}", api_key_id),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+
+    async fn update_api_key_with_options<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        params: &'a AdminUpdateApiKeyParams,
+        options: &'a RequestOptions,
     ) -> Result<ApiKey, AdminError> {
         self.post(
             &format!("/organizations/api_keys/{}", api_key_id),
             Some(params),
+            options,
+        )
+        .await
+    }
+
+    fn list_api_keys_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> impl Stream<Item = Result<ApiKey, AdminError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_api_keys(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl UsageReportClient for AnthropicClient {
+    /// Gets one page of the Messages API usage report
+    ///
+    /// Reports token usage of the organization bucketed by time. Requires an
+    /// Admin API key.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Time range, bucket width and pagination of the report
+    ///
+    /// # Returns
+    ///
+    /// Returns the usage buckets of the requested page on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::reporting::{
+    ///     BucketWidth, UsageGroupBy, UsageReportClient, UsageReportParams,
+    /// };
+    /// use time::{Duration, OffsetDateTime};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let now = OffsetDateTime::now_utc();
+    ///     let params = UsageReportParams::new(now - Duration::days(7))
+    ///         .ending_at(now)
+    ///         .bucket_width(BucketWidth::Day)
+    ///         .group_by(UsageGroupBy::Model);
+    ///     let report = client.get_usage_report(&params).await?;
+    ///     for bucket in report.data {
+    ///         for result in bucket.results {
+    ///             println!(
+    ///                 "{} {:?}: {} output tokens",
+    ///                 bucket.starting_at, result.model, result.output_tokens
+    ///             );
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_usage_report<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> Result<UsageReport, AdminError> {
+        self.get_usage_report_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn get_usage_report_paginated<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> impl Stream<Item = Result<UsageBucket, AdminError>> + 'a {
+        paginate(false, move |cursor| {
+            let mut params = params.clone();
+            if let Some(Cursor::Page(page)) = cursor {
+                params.page = Some(page);
+            }
+            async move {
+                let report = self.get_usage_report(&params).await?;
+                Ok(Page {
+                    data: report.data,
+                    has_more: report.has_more,
+                    first_id: None,
+                    last_id: None,
+                    next_page: report.next_page,
+                })
+            }
+        })
+    }
+
+    async fn get_usage_report_with_options<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+        options: &'a RequestOptions,
+    ) -> Result<UsageReport, AdminError> {
+        self.get(
+            "/organizations/usage_report/messages",
+            Some(&params.to_query()),
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl CostReportClient for AnthropicClient {
+    /// Gets one page of the cost report
+    ///
+    /// Reports the costs of the organization in one day buckets. Requires an
+    /// Admin API key.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Time range, grouping and pagination of the report
+    ///
+    /// # Returns
+    ///
+    /// Returns the cost buckets of the requested page on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::reporting::{
+    ///     CostGroupBy, CostReportClient, CostReportParams,
+    /// };
+    /// use time::{Duration, OffsetDateTime};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let now = OffsetDateTime::now_utc();
+    ///     let params = CostReportParams::new(now - Duration::days(30))
+    ///         .ending_at(now)
+    ///         .group_by(CostGroupBy::WorkspaceId);
+    ///     let report = client.get_cost_report(&params).await?;
+    ///     for bucket in report.data {
+    ///         for result in bucket.results {
+    ///             println!(
+    ///                 "{} {:?}: {} {}",
+    ///                 bucket.starting_at, result.workspace_id, result.amount, result.currency
+    ///             );
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_cost_report<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> Result<CostReport, AdminError> {
+        self.get_cost_report_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn get_cost_report_paginated<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> impl Stream<Item = Result<CostBucket, AdminError>> + 'a {
+        paginate(false, move |cursor| {
+            let mut params = params.clone();
+            if let Some(Cursor::Page(page)) = cursor {
+                params.page = Some(page);
+            }
+            async move {
+                let report = self.get_cost_report(&params).await?;
+                Ok(Page {
+                    data: report.data,
+                    has_more: report.has_more,
+                    first_id: None,
+                    last_id: None,
+                    next_page: report.next_page,
+                })
+            }
+        })
+    }
+
+    async fn get_cost_report_with_options<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+        options: &'a RequestOptions,
+    ) -> Result<CostReport, AdminError> {
+        self.get(
+            "/organizations/cost_report",
+            Some(&params.to_query()),
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl WorkspaceClient for AnthropicClient {
+    /// Lists workspaces
+    ///
+    /// Retrieves a list of the workspaces of the organization with pagination.
+    /// Archived workspaces are only included when requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for filtering and pagination
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of workspaces and pagination information on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::{ListWorkspacesParams, WorkspaceClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let params = ListWorkspacesParams::new().include_archived(true);
+    ///     let workspaces = client.list_workspaces(Some(&params)).await?;
+    ///     for workspace in workspaces.data {
+    ///         println!("Workspace: {} ({})", workspace.name, workspace.id);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn list_workspaces<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> Result<ListWorkspacesResponse, AdminError> {
+        self.list_workspaces_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn list_workspaces_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> impl Stream<Item = Result<Workspace, AdminError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_workspaces(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Gets a specific workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace to retrieve
+    ///
+    /// # Returns
+    ///
+    /// Returns the workspace details on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The workspace is not found
+    async fn get_workspace<'a>(&'a self, workspace_id: &'a str) -> Result<Workspace, AdminError> {
+        self.get_workspace_with_options(workspace_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Creates a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the created workspace on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::{CreateWorkspaceParams, WorkspaceClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let params = CreateWorkspaceParams::new("customer-acme");
+    ///     let workspace = client.create_workspace(&params).await?;
+    ///     println!("Created Workspace: {} ({})", workspace.name, workspace.id);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn create_workspace<'a>(
+        &'a self,
+        params: &'a CreateWorkspaceParams,
+    ) -> Result<Workspace, AdminError> {
+        self.create_workspace_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    /// Updates a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace to update
+    /// * `params` - Parameters for updating the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated workspace on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The workspace is not found
+    async fn update_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: &'a UpdateWorkspaceParams,
+    ) -> Result<Workspace, AdminError> {
+        self.update_workspace_with_options(workspace_id, params, &RequestOptions::default())
+            .await
+    }
+
+    /// Archives a workspace
+    ///
+    /// Archiving cannot be undone. The API keys of an archived workspace are
+    /// archived with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace to archive
+    ///
+    /// # Returns
+    ///
+    /// Returns the archived workspace on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The workspace is not found
+    async fn archive_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+    ) -> Result<Workspace, AdminError> {
+        self.archive_workspace_with_options(workspace_id, &RequestOptions::default())
+            .await
+    }
+
+    async fn list_workspaces_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListWorkspacesResponse, AdminError> {
+        self.get("/organizations/workspaces", params, options).await
+    }
+
+    async fn get_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        self.get(
+            &format!("/organizations/workspaces/{}", workspace_id),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+
+    async fn create_workspace_with_options<'a>(
+        &'a self,
+        params: &'a CreateWorkspaceParams,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        self.post("/organizations/workspaces", Some(params), options)
+            .await
+    }
+
+    async fn update_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: &'a UpdateWorkspaceParams,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        self.post(
+            &format!("/organizations/workspaces/{}", workspace_id),
+            Some(params),
+            options,
+        )
+        .await
+    }
+
+    async fn archive_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        self.post::<_, (), _>(
+            &format!("/organizations/workspaces/{}/archive", workspace_id),
+            None,
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl WorkspaceMemberClient for AnthropicClient {
+    /// Lists the members of a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `params` - Optional parameters for pagination
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of workspace members and pagination information on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The workspace is not found
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let members = client.list_workspace_members("wrkspc_xyz", None).await?;
+    ///     for member in members.data {
+    ///         println!("Member: {} ({:?})", member.user_id, member.workspace_role);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn list_workspace_members<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError> {
+        self.list_workspace_members_with_options(workspace_id, params, &RequestOptions::default())
+            .await
+    }
+
+    fn list_workspace_members_paginated<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> impl Stream<Item = Result<WorkspaceMember, AdminError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self
+                    .list_workspace_members(workspace_id, Some(&params))
+                    .await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Gets the membership of a user in a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the workspace member on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not a member of the workspace
+    async fn get_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.get_workspace_member_with_options(workspace_id, user_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Adds a user of the organization to a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `user_id` - The ID of the user to add
+    /// * `role` - Role of the user in the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the new workspace member on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspace_members::{
+    ///     WorkspaceMemberClient, WorkspaceRole,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let member = client
+    ///         .add_workspace_member("wrkspc_xyz", "user_xyz", WorkspaceRole::Developer)
+    ///         .await?;
+    ///     println!("Added {} to {}", member.user_id, member.workspace_id);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn add_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.add_workspace_member_with_options(
+            workspace_id,
+            user_id,
+            role,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Changes the role of a user in a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `user_id` - The ID of the user
+    /// * `role` - New role of the user in the workspace
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated workspace member on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not a member of the workspace
+    async fn update_workspace_member_role<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.update_workspace_member_role_with_options(
+            workspace_id,
+            user_id,
+            role,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Removes a user from a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `user_id` - The ID of the user to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns confirmation of the removal on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not a member of the workspace
+    async fn delete_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMemberDeleted, AdminError> {
+        self.delete_workspace_member_with_options(workspace_id, user_id, &RequestOptions::default())
+            .await
+    }
+
+    async fn list_workspace_members_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError> {
+        self.get(
+            &format!("/organizations/workspaces/{}/members", workspace_id),
+            params,
+            options,
+        )
+        .await
+    }
+
+    async fn get_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        self.get(
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+
+    async fn add_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        let body = AddWorkspaceMemberBody {
+            user_id,
+            workspace_role: role,
+        };
+        self.post(
+            &format!("/organizations/workspaces/{}/members", workspace_id),
+            Some(&body),
+            options,
+        )
+        .await
+    }
+
+    async fn update_workspace_member_role_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        let body = UpdateWorkspaceMemberBody {
+            workspace_role: role,
+        };
+        self.post(
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            Some(&body),
+            options,
+        )
+        .await
+    }
+
+    async fn delete_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMemberDeleted, AdminError> {
+        self.delete(
+            &format!(
+                "/organizations/workspaces/{}/members/{}",
+                workspace_id, user_id
+            ),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl UserClient for AnthropicClient {
+    /// Lists the users of the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for filtering and pagination
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of users and pagination information on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::UserClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let users = client.list_users(None).await?;
+    ///     for user in users.data {
+    ///         println!("User: {} <{}> ({:?})", user.name, user.email, user.role);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn list_users<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+    ) -> Result<ListUsersResponse, AdminError> {
+        self.list_users_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn list_users_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+    ) -> impl Stream<Item = Result<OrganizationUser, AdminError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_users(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Gets a user of the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user to retrieve
+    ///
+    /// # Returns
+    ///
+    /// Returns the user on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not found
+    async fn get_user<'a>(&'a self, user_id: &'a str) -> Result<OrganizationUser, AdminError> {
+        self.get_user_with_options(user_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Changes the role of a user in the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user to update
+    /// * `role` - New role of the user
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated user on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not found
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::users::{OrganizationRole, UserClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let user = client
+    ///         .update_user_role("user_xyz", OrganizationRole::Developer)
+    ///         .await?;
+    ///     println!("{} is now {:?}", user.email, user.role);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn update_user_role<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+    ) -> Result<OrganizationUser, AdminError> {
+        self.update_user_role_with_options(user_id, role, &RequestOptions::default())
+            .await
+    }
+
+    /// Removes a user from the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns confirmation of the removal on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The user is not found
+    async fn remove_user<'a>(&'a self, user_id: &'a str) -> Result<UserDeleted, AdminError> {
+        self.remove_user_with_options(user_id, &RequestOptions::default())
+            .await
+    }
+
+    async fn list_users_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListUsersResponse, AdminError> {
+        self.get("/organizations/users", params, options).await
+    }
+
+    async fn get_user_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<OrganizationUser, AdminError> {
+        self.get(
+            &format!("/organizations/users/{}", user_id),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+
+    async fn update_user_role_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+        options: &'a RequestOptions,
+    ) -> Result<OrganizationUser, AdminError> {
+        self.post(
+            &format!("/organizations/users/{}", user_id),
+            Some(&UpdateUserBody { role }),
+            options,
+        )
+        .await
+    }
+
+    async fn remove_user_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<UserDeleted, AdminError> {
+        self.delete(
+            &format!("/organizations/users/{}", user_id),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl InviteClient for AnthropicClient {
+    /// Lists the invites of the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Optional parameters for pagination
+    ///
+    /// # Returns
+    ///
+    /// Returns a list of invites and pagination information on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    async fn list_invites<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> Result<ListInvitesResponse, AdminError> {
+        self.list_invites_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn list_invites_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> impl Stream<Item = Result<Invite, AdminError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_invites(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Gets an invite
+    ///
+    /// # Arguments
+    ///
+    /// * `invite_id` - The ID of the invite to retrieve
+    ///
+    /// # Returns
+    ///
+    /// Returns the invite on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The invite is not found
+    async fn get_invite<'a>(&'a self, invite_id: &'a str) -> Result<Invite, AdminError> {
+        self.get_invite_with_options(invite_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Invites a user to the organization
+    ///
+    /// # Arguments
+    ///
+    /// * `email` - Email of the user to invite
+    /// * `role` - Role the user will have in the organization
+    ///
+    /// # Returns
+    ///
+    /// Returns the created invite on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::invites::InviteClient;
+    /// use anthropic_ai_sdk::types::admin::users::OrganizationRole;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), AdminError> {
+    ///     let client = AnthropicClient::new_admin::<AdminError>(
+    ///         "your-admin-api-key",
+    ///         "2023-06-01",
+    ///     )?;
+    ///
+    ///     let invite = client
+    ///         .create_invite("new.hire@example.com", OrganizationRole::User)
+    ///         .await?;
+    ///     println!("Invite {} expires at {}", invite.id, invite.expires_at);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn create_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError> {
+        self.create_invite_with_options(email, role, &RequestOptions::default())
+            .await
+    }
+
+    /// Deletes a pending invite
+    ///
+    /// # Arguments
+    ///
+    /// * `invite_id` - The ID of the invite to delete
+    ///
+    /// # Returns
+    ///
+    /// Returns confirmation of the deletion on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AdminError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    /// - The invite is not found
+    async fn delete_invite<'a>(&'a self, invite_id: &'a str) -> Result<InviteDeleted, AdminError> {
+        self.delete_invite_with_options(invite_id, &RequestOptions::default())
+            .await
+    }
+
+    async fn list_invites_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListInvitesResponse, AdminError> {
+        self.get("/organizations/invites", params, options).await
+    }
+
+    async fn get_invite_with_options<'a>(
+        &'a self,
+        invite_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Invite, AdminError> {
+        self.get(
+            &format!("/organizations/invites/{}", invite_id),
+            Option::<&()>::None,
+            options,
+        )
+        .await
+    }
+
+    async fn create_invite_with_options<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+        options: &'a RequestOptions,
+    ) -> Result<Invite, AdminError> {
+        self.post(
+            "/organizations/invites",
+            Some(&CreateInviteBody { email, role }),
+            options,
+        )
+        .await
+    }
+
+    async fn delete_invite_with_options<'a>(
+        &'a self,
+        invite_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<InviteDeleted, AdminError> {
+        self.delete(
+            &format!("/organizations/invites/{}", invite_id),
+            Option::<&()>::None,
+            options,
         )
         .await
     }
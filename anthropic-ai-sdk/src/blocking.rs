@@ -0,0 +1,436 @@
+//! Blocking Anthropic API client
+//!
+//! This module provides a synchronous client for programs that do not run an
+//! async executor, such as small CLIs and build scripts. It is enabled with
+//! the `blocking` feature.
+//!
+//! The blocking client wraps the async [`crate::client::AnthropicClient`], so
+//! retries, rate limiting and the `count_tokens` cache behave the same. Its
+//! connections and timers run on a background runtime with one worker thread,
+//! while each call blocks the calling thread until it completes. Calls made
+//! from within an async context do not panic, but they block that executor
+//! thread, so async code should use the async client instead.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use anthropic_ai_sdk::blocking::AnthropicClient;
+//! use anthropic_ai_sdk::types::message::{
+//!     CreateMessageParams, Message, MessageError, RequiredMessageParams,
+//! };
+//!
+//! fn main() -> Result<(), MessageError> {
+//!     let client = AnthropicClient::new::<MessageError>("your-api-key", "2023-06-01")?;
+//!
+//!     let body = CreateMessageParams::new(RequiredMessageParams {
+//!         model: "claude-3-5-sonnet-20240620".to_string(),
+//!         messages: vec![Message::user("Hello, Claude")],
+//!         max_tokens: 1024,
+//!     });
+//!
+//!     let message = client.create_message(Some(&body))?;
+//!     println!("{}", message.text());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::client::AnthropicClient as AsyncClient;
+use crate::types::admin::api_keys::{
+    AdminClient, AdminError, AdminUpdateApiKeyParams, ApiKey, ListApiKeysParams,
+    ListApiKeysResponse,
+};
+use crate::types::files::{
+    FileDeleted, FileError, FileMetadata, FilesClient, ListFilesParams, ListFilesResponse,
+};
+use crate::types::message::{
+    CountMessageTokensParams, CountMessageTokensResponse, CreateMessageParams,
+    CreateMessageResponse, MessageClient, MessageError, StreamEvent,
+};
+use crate::types::message_batches::{
+    CancelMessageBatchParams, CancelResponse, CreateMessageBatchParams, DeleteMessageBatchParams,
+    DeleteResponse, ListMessageBatchesParams, ListMessageBatchesResponse, MessageBatch,
+    MessageBatchClient, MessageBatchError, MessageBatchResult, PollConfig, RequestCounts,
+    RetrieveMessageBatchParams, RetrieveMessageBatchResponse, RetrieveMessageBatchResultsParams,
+    RetrieveMessageBatchResultsResponse,
+};
+use crate::types::model::{ListModelsParams, ListModelsResponse, Model, ModelClient, ModelError};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use std::error::Error as StdError;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::runtime::{Handle, Runtime};
+
+/// Blocking Anthropic API client
+///
+/// Cloning the client is cheap: clones share the async client and the runtime.
+#[derive(Debug, Clone)]
+pub struct AnthropicClient {
+    /// The async client doing the actual work
+    inner: AsyncClient,
+    /// The runtime driving the async client's connections and timers
+    runtime: Arc<BackgroundRuntime>,
+}
+
+/// Owns the client's runtime and shuts it down without waiting, so that the
+/// last clone of the client can be dropped from any context
+#[derive(Debug)]
+struct BackgroundRuntime {
+    runtime: Option<Runtime>,
+    handle: Handle,
+}
+
+impl BackgroundRuntime {
+    /// Polls a future on the current thread until it completes
+    ///
+    /// The runtime is entered so that sockets and timers created by the future
+    /// register with it; its worker thread drives them in the meantime.
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let _guard = self.handle.enter();
+        futures_lite::future::block_on(future)
+    }
+}
+
+impl Drop for BackgroundRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Iterator over the items of a stream, each fetched by blocking the calling
+/// thread
+pub struct StreamIter<'a, T> {
+    runtime: &'a BackgroundRuntime,
+    stream: Pin<Box<dyn Stream<Item = T> + 'a>>,
+}
+
+impl<T> Iterator for StreamIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl AnthropicClient {
+    /// Creates a new blocking client with the specified credentials
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - Your Anthropic API key for authentication
+    /// * `api_version` - The API version to use (e.g., "2023-06-01")
+    pub fn new<E>(api_key: impl Into<String>, api_version: impl Into<String>) -> Result<Self, E>
+    where
+        E: StdError + From<String>,
+    {
+        Self::from_async(AsyncClient::new::<E>(api_key, api_version)?)
+    }
+
+    /// Creates a new blocking Admin API client with the specified credentials
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_api_key` - Your Anthropic Admin API key for authentication
+    /// * `api_version` - The API version to use (e.g., "2023-06-01")
+    pub fn new_admin<E>(
+        admin_api_key: impl Into<String>,
+        api_version: impl Into<String>,
+    ) -> Result<Self, E>
+    where
+        E: StdError + From<String>,
+    {
+        Self::from_async(AsyncClient::new_admin::<E>(admin_api_key, api_version)?)
+    }
+
+    /// Wraps an async client configured with `AnthropicClient::builder`
+    ///
+    /// The async client should not also be used from async code: its pooled
+    /// connections belong to the runtime they were opened on.
+    pub fn from_async<E>(client: AsyncClient) -> Result<Self, E>
+    where
+        E: StdError + From<String>,
+    {
+        // The worker thread keeps the connections progressing between calls
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .map_err(|e| E::from(format!("Failed to start the runtime: {}", e)))?;
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(BackgroundRuntime {
+                handle: runtime.handle().clone(),
+                runtime: Some(runtime),
+            }),
+        })
+    }
+
+    /// Returns the wrapped async client
+    pub fn inner(&self) -> &AsyncClient {
+        &self.inner
+    }
+
+    /// Runs a future with the client's runtime and waits for its output
+    ///
+    /// This gives access to the calls that have no blocking counterpart, such
+    /// as the `*_with_options` variants or the other Admin APIs:
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::blocking::AnthropicClient;
+    /// use anthropic_ai_sdk::types::admin::api_keys::AdminError;
+    /// use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+    ///
+    /// let client = AnthropicClient::new_admin::<AdminError>("your-admin-api-key", "2023-06-01")?;
+    /// let workspaces = client.block_on(client.inner().list_workspaces(None))?;
+    /// # Ok::<(), AdminError>(())
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    fn iter<'a, T>(&'a self, stream: impl Stream<Item = T> + 'a) -> StreamIter<'a, T> {
+        StreamIter {
+            runtime: &self.runtime,
+            stream: Box::pin(stream),
+        }
+    }
+
+    /// Creates a message, see `MessageClient::create_message`
+    pub fn create_message(
+        &self,
+        params: Option<&CreateMessageParams>,
+    ) -> Result<CreateMessageResponse, MessageError> {
+        self.block_on(self.inner.create_message(params))
+    }
+
+    /// Counts the tokens of a message, see `MessageClient::count_tokens`
+    pub fn count_tokens(
+        &self,
+        params: Option<&CountMessageTokensParams>,
+    ) -> Result<CountMessageTokensResponse, MessageError> {
+        self.block_on(self.inner.count_tokens(params))
+    }
+
+    /// Creates a message and iterates over its stream events, see
+    /// `MessageClient::create_message_streaming`
+    pub fn create_message_streaming<'a>(
+        &'a self,
+        body: &'a CreateMessageParams,
+    ) -> Result<StreamIter<'a, Result<StreamEvent, MessageError>>, MessageError> {
+        let stream = self.block_on(self.inner.create_message_streaming(body))?;
+        Ok(self.iter(stream))
+    }
+
+    /// Creates messages concurrently and iterates over the results, see
+    /// `MessageClient::create_messages_concurrent`
+    pub fn create_messages_concurrent<'a, I>(
+        &'a self,
+        params: I,
+        concurrency: usize,
+    ) -> StreamIter<'a, Result<CreateMessageResponse, MessageError>>
+    where
+        I: IntoIterator<Item = CreateMessageParams>,
+        I::IntoIter: 'a,
+    {
+        self.iter(self.inner.create_messages_concurrent(params, concurrency))
+    }
+
+    /// Lists models, see `ModelClient::list_models`
+    pub fn list_models(
+        &self,
+        params: Option<&ListModelsParams>,
+    ) -> Result<ListModelsResponse, ModelError> {
+        self.block_on(self.inner.list_models(params))
+    }
+
+    /// Iterates over all models, see `ModelClient::list_models_paginated`
+    pub fn list_models_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> StreamIter<'a, Result<Model, ModelError>> {
+        self.iter(self.inner.list_models_paginated(params))
+    }
+
+    /// Gets a model, see `ModelClient::get_model`
+    pub fn get_model(&self, model_id: &str) -> Result<Model, ModelError> {
+        self.block_on(self.inner.get_model(model_id))
+    }
+
+    /// Resolves a model alias, see `ModelClient::resolve_model_alias`
+    pub fn resolve_model_alias(&self, alias: &str) -> Result<Model, ModelError> {
+        self.block_on(self.inner.resolve_model_alias(alias))
+    }
+
+    /// Creates a message batch, see `MessageBatchClient::create_message_batch`
+    pub fn create_message_batch(
+        &self,
+        params: &CreateMessageBatchParams,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        self.block_on(self.inner.create_message_batch(params))
+    }
+
+    /// Lists message batches, see `MessageBatchClient::list_message_batches`
+    pub fn list_message_batches(
+        &self,
+        params: Option<&ListMessageBatchesParams>,
+    ) -> Result<ListMessageBatchesResponse, MessageBatchError> {
+        self.block_on(self.inner.list_message_batches(params))
+    }
+
+    /// Iterates over all message batches, see
+    /// `MessageBatchClient::list_message_batches_paginated`
+    pub fn list_message_batches_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+    ) -> StreamIter<'a, Result<MessageBatch, MessageBatchError>> {
+        self.iter(self.inner.list_message_batches_paginated(params))
+    }
+
+    /// Retrieves a message batch, see `MessageBatchClient::retrieve_message_batch`
+    pub fn retrieve_message_batch(
+        &self,
+        params: &RetrieveMessageBatchParams,
+    ) -> Result<RetrieveMessageBatchResponse, MessageBatchError> {
+        self.block_on(self.inner.retrieve_message_batch(params))
+    }
+
+    /// Retrieves the results of a message batch, see
+    /// `MessageBatchClient::retrieve_message_batch_results`
+    pub fn retrieve_message_batch_results(
+        &self,
+        params: &RetrieveMessageBatchResultsParams,
+    ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError> {
+        self.block_on(self.inner.retrieve_message_batch_results(params))
+    }
+
+    /// Iterates over the results of a message batch, see
+    /// `MessageBatchClient::stream_message_batch_results`
+    pub fn stream_message_batch_results<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+    ) -> Result<StreamIter<'a, Result<MessageBatchResult, MessageBatchError>>, MessageBatchError>
+    {
+        let stream = self.block_on(self.inner.stream_message_batch_results(message_batch_id))?;
+        Ok(self.iter(stream))
+    }
+
+    /// Cancels a message batch, see `MessageBatchClient::cancel_message_batch`
+    pub fn cancel_message_batch(
+        &self,
+        params: &CancelMessageBatchParams,
+    ) -> Result<CancelResponse, MessageBatchError> {
+        self.block_on(self.inner.cancel_message_batch(params))
+    }
+
+    /// Deletes a message batch, see `MessageBatchClient::delete_message_batch`
+    pub fn delete_message_batch(
+        &self,
+        params: &DeleteMessageBatchParams,
+    ) -> Result<DeleteResponse, MessageBatchError> {
+        self.block_on(self.inner.delete_message_batch(params))
+    }
+
+    /// Waits until a message batch has ended, see
+    /// `MessageBatchClient::wait_for_batch`
+    pub fn wait_for_batch(
+        &self,
+        message_batch_id: &str,
+        config: PollConfig,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        self.block_on(self.inner.wait_for_batch(message_batch_id, config))
+    }
+
+    /// Waits until a message batch has ended, reporting its progress, see
+    /// `MessageBatchClient::wait_for_batch_with_progress`
+    pub fn wait_for_batch_with_progress(
+        &self,
+        message_batch_id: &str,
+        config: PollConfig,
+        on_progress: &mut (dyn for<'b> FnMut(&'b RequestCounts) + Send),
+    ) -> Result<MessageBatch, MessageBatchError> {
+        self.block_on(self.inner.wait_for_batch_with_progress(
+            message_batch_id,
+            config,
+            on_progress,
+        ))
+    }
+
+    /// Uploads a file, see `FilesClient::upload_file`
+    pub fn upload_file(
+        &self,
+        data: Bytes,
+        filename: &str,
+        media_type: &str,
+    ) -> Result<FileMetadata, FileError> {
+        self.block_on(self.inner.upload_file(data, filename, media_type))
+    }
+
+    /// Lists files, see `FilesClient::list_files`
+    pub fn list_files(
+        &self,
+        params: Option<&ListFilesParams>,
+    ) -> Result<ListFilesResponse, FileError> {
+        self.block_on(self.inner.list_files(params))
+    }
+
+    /// Iterates over all files, see `FilesClient::list_files_paginated`
+    pub fn list_files_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> StreamIter<'a, Result<FileMetadata, FileError>> {
+        self.iter(self.inner.list_files_paginated(params))
+    }
+
+    /// Gets the metadata of a file, see `FilesClient::get_file_metadata`
+    pub fn get_file_metadata(&self, file_id: &str) -> Result<FileMetadata, FileError> {
+        self.block_on(self.inner.get_file_metadata(file_id))
+    }
+
+    /// Deletes a file, see `FilesClient::delete_file`
+    pub fn delete_file(&self, file_id: &str) -> Result<FileDeleted, FileError> {
+        self.block_on(self.inner.delete_file(file_id))
+    }
+
+    /// Downloads the content of a file, see `FilesClient::download_file`
+    pub fn download_file(&self, file_id: &str) -> Result<Bytes, FileError> {
+        self.block_on(self.inner.download_file(file_id))
+    }
+
+    /// Lists API keys, see `AdminClient::list_api_keys`
+    pub fn list_api_keys(
+        &self,
+        params: Option<&ListApiKeysParams>,
+    ) -> Result<ListApiKeysResponse, AdminError> {
+        self.block_on(self.inner.list_api_keys(params))
+    }
+
+    /// Iterates over all API keys, see `AdminClient::list_api_keys_paginated`
+    pub fn list_api_keys_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> StreamIter<'a, Result<ApiKey, AdminError>> {
+        self.iter(self.inner.list_api_keys_paginated(params))
+    }
+
+    /// Gets an API key, see `AdminClient::get_api_key`
+    pub fn get_api_key(&self, api_key_id: &str) -> Result<ApiKey, AdminError> {
+        self.block_on(AdminClient::get_api_key(&self.inner, api_key_id))
+    }
+
+    /// Updates an API key, see `AdminClient::update_api_key`
+    pub fn update_api_key(
+        &self,
+        api_key_id: &str,
+        params: &AdminUpdateApiKeyParams,
+    ) -> Result<ApiKey, AdminError> {
+        self.block_on(self.inner.update_api_key(api_key_id, params))
+    }
+
+    /// Archives an API key, see `AdminClient::archive_api_key`
+    pub fn archive_api_key(&self, api_key_id: &str) -> Result<ApiKey, AdminError> {
+        self.block_on(self.inner.archive_api_key(api_key_id))
+    }
+}
@@ -0,0 +1,93 @@
+//! Token count cache
+//!
+//! This module contains the bounded cache used by `count_tokens` when it is
+//! enabled with `AnthropicClientBuilder::with_count_tokens_cache`.
+
+use crate::timer::Instant;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// LRU cache of token counts with a time to live
+///
+/// Entries are keyed on a hash of the canonical JSON of the request body.
+#[derive(Debug)]
+pub(crate) struct TokenCountCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<u64, CacheEntry>,
+    /// Monotonic counter used to find the least recently used entry
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    input_tokens: u32,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+impl TokenCountCache {
+    pub(crate) fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Computes the cache key of a request body
+    ///
+    /// `serde_json::Value` keeps object keys sorted, so equal requests always
+    /// produce the same string.
+    pub(crate) fn key(body: &serde_json::Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        body.to_string().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the cached count, dropping it if it has expired
+    pub(crate) fn get(&mut self, key: u64) -> Option<u32> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&key)?;
+        if entry.inserted_at.elapsed() > self.ttl {
+            self.entries.remove(&key);
+            return None;
+        }
+        entry.last_used = self.clock;
+        Some(entry.input_tokens)
+    }
+
+    /// Stores a count, evicting the least recently used entry when full
+    pub(crate) fn insert(&mut self, key: u64, input_tokens: u32) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, entry| entry.inserted_at.elapsed() <= ttl);
+            if self.entries.len() >= self.capacity {
+                if let Some(oldest) = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| *key)
+                {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                input_tokens,
+                inserted_at: Instant::now(),
+                last_used: self.clock,
+            },
+        );
+    }
+}
@@ -3,10 +3,34 @@
 //! This module provides the main client for interacting with the Anthropic API.
 //! It handles authentication, request construction, and response parsing.
 
+use crate::cache::TokenCountCache;
+use crate::platform::Platform;
+use crate::rate_limit::RateLimiter;
+use crate::timer;
+use crate::types::error::{ApiErrorResponse, ResponseTooLarge};
+use bytes::Bytes;
+use futures_util::StreamExt;
 use reqwest::Client as ReqwestClient;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use reqwest::StatusCode;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::dns::{Name, Resolve, Resolving};
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use reqwest::multipart::{Form, Part};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
 use std::error::Error as StdError;
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::{IpAddr, SocketAddr};
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
 
 /// Anthropic API client
 ///
@@ -36,10 +60,38 @@ use std::error::Error as StdError;
 /// let client_with_custom_http = AnthropicClient::builder("your-api-key", "2023-06-01")
 ///     .with_http_client(reqwest_client)
 ///     .build::<ModelError>()?;
+///
+/// // Bounding how long a request may take
+/// let client_with_timeouts = AnthropicClient::builder("your-api-key", "2023-06-01")
+///     .with_timeout(std::time::Duration::from_secs(600))
+///     .with_connect_timeout(std::time::Duration::from_secs(10))
+///     .build::<ModelError>()?;
+///
+/// // Binding outgoing connections to a local interface
+/// let client_with_local_address = AnthropicClient::builder("your-api-key", "2023-06-01")
+///     .with_local_address("10.0.0.2".parse()?)
+///     .build::<ModelError>()?;
+///
+/// // Retrying rate-limited and overloaded requests
+/// let client_with_retries = AnthropicClient::builder("your-api-key", "2023-06-01")
+///     .with_max_retries(3)
+///     .with_max_retry_delay(std::time::Duration::from_secs(30))
+///     .build::<ModelError>()?;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+///
+/// The API key is redacted from the `Debug` output, so that the client can be
+/// logged safely:
+///
+/// ```
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::types::model::ModelError;
+///
+/// let client = AnthropicClient::new::<ModelError>("sk-ant-secret", "2023-06-01").unwrap();
+/// assert!(!format!("{client:?}").contains("sk-ant-secret"));
+/// ```
+#[derive(Clone)]
 pub struct AnthropicClient {
     /// The underlying HTTP client for making requests
     client: ReqwestClient,
@@ -49,16 +101,142 @@ pub struct AnthropicClient {
     api_version: String,
     /// The base URL for the Anthropic API
     api_base_url: String,
+    /// Maximum number of times a failed request is retried
+    max_retries: u32,
+    /// Upper bound for the delay between two attempts
+    max_retry_delay: Duration,
+    /// Cache of `count_tokens` results, shared between clones
+    count_tokens_cache: Option<Arc<Mutex<TokenCountCache>>>,
+    /// Rate limit state of the last response, shared between clones
+    last_rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// Beta features sent in the `anthropic-beta` header of every request
+    betas: Vec<String>,
+    /// Extra headers sent with every request
+    default_headers: HeaderMap,
+    /// Client-side limit of outbound requests, shared between clones
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Whether an idempotency key is generated for every request
+    idempotency_keys: bool,
+    /// Whether the `anthropic-version` header is sent with every request
+    version_header: bool,
+    /// Platform serving the requests
+    platform: Platform,
+    /// Hook adding the authentication headers of every request
+    auth_header_fn: Option<Arc<AuthHeaderFn>>,
+    /// Hook called with every request before it is sent
+    request_inspector: Option<Arc<RequestInspectorFn>>,
+    /// Hook called with the status and headers of every response
+    response_inspector: Option<Arc<ResponseInspectorFn>>,
+    /// Maximum size of a response body read into memory
+    max_response_bytes: Option<usize>,
+}
+
+/// Hook computing the authentication headers of a request
+///
+/// See `AnthropicClientBuilder::with_auth_header_fn`.
+pub type AuthHeaderFn = dyn Fn(&reqwest::Request) -> Result<HeaderMap, String> + Send + Sync;
+
+/// Hook inspecting a request before it is sent
+///
+/// See `AnthropicClientBuilder::with_request_inspector`.
+pub type RequestInspectorFn = dyn Fn(&reqwest::Request) + Send + Sync;
+
+/// Hook inspecting the status and headers of a response before it is parsed
+///
+/// See `AnthropicClientBuilder::with_response_inspector`.
+pub type ResponseInspectorFn = dyn Fn(&StatusCode, &HeaderMap) + Send + Sync;
+
+impl fmt::Debug for AnthropicClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnthropicClient")
+            .field("client", &self.client)
+            .field("api_key", &"[REDACTED]")
+            .field("api_version", &self.api_version)
+            .field("api_base_url", &self.api_base_url)
+            .field("max_retries", &self.max_retries)
+            .field("max_retry_delay", &self.max_retry_delay)
+            .field("count_tokens_cache", &self.count_tokens_cache)
+            .field("last_rate_limit", &self.last_rate_limit)
+            .field("betas", &self.betas)
+            .field("default_headers", &self.default_headers)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("idempotency_keys", &self.idempotency_keys)
+            .field("version_header", &self.version_header)
+            .field("platform", &self.platform)
+            .field(
+                "auth_header_fn",
+                &self.auth_header_fn.as_ref().map(|_| "Fn"),
+            )
+            .field(
+                "request_inspector",
+                &self.request_inspector.as_ref().map(|_| "Fn"),
+            )
+            .field(
+                "response_inspector",
+                &self.response_inspector.as_ref().map(|_| "Fn"),
+            )
+            .field("max_response_bytes", &self.max_response_bytes)
+            .finish()
+    }
+}
+
+/// Response of [`AnthropicClient::request_raw`]
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    /// HTTP status code of the response
+    pub status: StatusCode,
+    /// Headers of the response
+    pub headers: HeaderMap,
+    /// Unparsed body of the response
+    pub body: Bytes,
+}
+
+impl RawResponse {
+    /// Parses the body as JSON
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.body)
+    }
 }
 
 /// Builder for AnthropicClient
 ///
 /// Provides a flexible way to configure and create an AnthropicClient.
+///
+/// On wasm32, the options that the browser's `fetch` cannot honor are not
+/// available: `with_timeout`, `with_connect_timeout`, `with_local_address`,
+/// `with_proxy`, `with_resolve` and `with_dns_resolver`. A timeout can still
+/// be set per request with `RequestOptions::with_timeout`.
 pub struct AnthropicClientBuilder {
     api_key: String,
     api_version: String,
     api_base_url: String,
     client: Option<ReqwestClient>,
+    max_retries: u32,
+    max_retry_delay: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    local_address: Option<IpAddr>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxies: Vec<Proxy>,
+    #[cfg(not(target_arch = "wasm32"))]
+    dns_overrides: HashMap<String, Vec<SocketAddr>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    dns_resolver: Option<Arc<dyn Resolve>>,
+    count_tokens_cache: Option<(usize, Duration)>,
+    betas: Vec<String>,
+    default_headers: HeaderMap,
+    rate_limit: Option<u32>,
+    idempotency_keys: bool,
+    version_header: bool,
+    platform: Platform,
+    auth_header_fn: Option<Arc<AuthHeaderFn>>,
+    request_inspector: Option<Arc<RequestInspectorFn>>,
+    response_inspector: Option<Arc<ResponseInspectorFn>>,
+    max_response_bytes: Option<usize>,
 }
 
 impl AnthropicClientBuilder {
@@ -69,6 +247,32 @@ impl AnthropicClientBuilder {
             api_version: api_version.into(),
             api_base_url: AnthropicClient::DEFAULT_API_BASE_URL.to_string(),
             client: None,
+            max_retries: AnthropicClient::DEFAULT_MAX_RETRIES,
+            max_retry_delay: AnthropicClient::DEFAULT_MAX_RETRY_DELAY,
+            #[cfg(not(target_arch = "wasm32"))]
+            timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            connect_timeout: None,
+            user_agent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            local_address: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxies: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dns_overrides: HashMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dns_resolver: None,
+            count_tokens_cache: None,
+            betas: Vec::new(),
+            default_headers: HeaderMap::new(),
+            rate_limit: None,
+            idempotency_keys: false,
+            version_header: true,
+            platform: Platform::Anthropic,
+            auth_header_fn: None,
+            request_inspector: None,
+            response_inspector: None,
+            max_response_bytes: None,
         }
     }
 
@@ -79,6 +283,12 @@ impl AnthropicClientBuilder {
     }
 
     /// Sets a custom HTTP client
+    ///
+    /// The client is used as is: options that configure the internally built
+    /// client, such as `with_timeout`, `with_connect_timeout` and `with_proxy`,
+    /// are ignored in favor of the custom client's own settings. The
+    /// authentication, version and beta headers are added to each request, so
+    /// they apply to a custom client too.
     pub fn with_http_client(mut self, client: ReqwestClient) -> Self {
         self.client = Some(client);
         self
@@ -90,19 +300,369 @@ impl AnthropicClientBuilder {
         self
     }
 
+    /// Sets how many times a request is retried after a retryable failure
+    ///
+    /// Rate limited (429), overloaded (529) and other server errors, as well as
+    /// connection errors and timeouts, are retried. Retries are disabled by default.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the upper bound for the delay between two attempts
+    ///
+    /// Applies to both the computed exponential backoff and the delay requested
+    /// by the server through the `retry-after` header.
+    pub fn with_max_retry_delay(mut self, max_retry_delay: Duration) -> Self {
+        self.max_retry_delay = max_retry_delay;
+        self
+    }
+
+    /// Sets a timeout for each request, from sending it until the response body is read
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for establishing the connection only
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request
+    ///
+    /// Defaults to `AnthropicClient::DEFAULT_USER_AGENT`, which names the crate
+    /// and its version. Ignored when a custom HTTP client is set with
+    /// `with_http_client`, whose own user agent is sent.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Binds outgoing connections to the given local address
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_local_address(mut self, local_address: IpAddr) -> Self {
+        self.local_address = Some(local_address);
+        self
+    }
+
+    /// Sends requests through a proxy
+    ///
+    /// Can be called several times; the first proxy that intercepts a URL is
+    /// used. Without any, the system proxy settings (`HTTPS_PROXY` and friends)
+    /// apply. Authentication, version and beta headers are still added to every
+    /// request.
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`: set the
+    /// proxy on that client instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let proxy = reqwest::Proxy::https("http://proxy.internal:3128")?
+    ///     .basic_auth("user", "password");
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_proxy(proxy)
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Resolves `domain` to the given address instead of using DNS
+    ///
+    /// Can be called several times for the same domain to provide several
+    /// addresses. The port of the address is ignored in favor of the port of
+    /// the request URL.
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.dns_overrides
+            .entry(domain.into().to_ascii_lowercase())
+            .or_default()
+            .push(addr);
+        self
+    }
+
+    /// Caches `count_tokens` results
+    ///
+    /// Identical requests made within `ttl` are answered from the cache
+    /// without contacting the API. At most `capacity` counts are kept, and the
+    /// least recently used one is evicted first. Requests with custom headers,
+    /// betas or API version in their `RequestOptions` bypass the cache.
+    /// Disabled by default.
+    pub fn with_count_tokens_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.count_tokens_cache = Some((capacity, ttl));
+        self
+    }
+
+    /// Enables a beta feature on every request
+    ///
+    /// Can be called several times: the features are sent together as a
+    /// comma-separated `anthropic-beta` header. Betas set on `RequestOptions`
+    /// are added to these.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// # fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_beta("token-efficient-tools-2025-02-19")
+    ///     .with_beta("output-128k-2025-02-19")
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_beta(mut self, feature: impl Into<String>) -> Self {
+        self.betas.push(feature.into());
+        self
+    }
+
+    /// Adds a header to every request
+    ///
+    /// Setting the same header again replaces its value. Default headers never
+    /// replace `x-api-key` or `anthropic-version`, while headers set on
+    /// `RequestOptions` override them for a single request.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// use reqwest::header::{HeaderName, HeaderValue};
+    ///
+    /// # fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_default_header(
+    ///         HeaderName::from_static("x-tenant-id"),
+    ///         HeaderValue::from_static("tenant-1"),
+    ///     )
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Limits the number of requests sent per minute
+    ///
+    /// Every outbound request, including retries and streaming requests, waits
+    /// for a permit from a token bucket holding `requests_per_minute` permits
+    /// and refilled at that rate. Clones of the client share the bucket.
+    /// Disabled by default.
+    pub fn with_rate_limit(mut self, requests_per_minute: u32) -> Self {
+        self.rate_limit = Some(requests_per_minute);
+        self
+    }
+
+    /// Sends a generated idempotency key with every request
+    ///
+    /// Each call gets a new random UUID in the `idempotency-key` header, and
+    /// its retries resend the same value, so that a gateway in front of the
+    /// API can recognize duplicates. A key set with
+    /// `RequestOptions::with_idempotency_key` takes precedence. Disabled by
+    /// default.
+    pub fn with_idempotency_keys(mut self, enabled: bool) -> Self {
+        self.idempotency_keys = enabled;
+        self
+    }
+
+    /// Stops sending the `anthropic-version` header
+    ///
+    /// This is an advanced option for gateways that set the API version
+    /// themselves and reject requests that already carry it. The header is
+    /// still sent when a request sets a version with
+    /// `RequestOptions::with_api_version`.
+    pub fn without_version_header(mut self) -> Self {
+        self.version_header = false;
+        self
+    }
+
+    /// Sends the requests to the given platform
+    ///
+    /// This also sets the API base URL to the platform's, so call
+    /// `with_api_base_url` afterwards to go through a gateway. See
+    /// `AnthropicClient::bedrock` and `AnthropicClient::vertex`.
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.api_base_url = platform.api_base_url();
+        self.platform = platform;
+        self
+    }
+
+    /// Computes authentication headers for every request
+    ///
+    /// The hook is called with each request right before it is sent, retries
+    /// included, and the headers it returns are added to the request,
+    /// replacing those with the same name. This is where a SigV4 signature for
+    /// Amazon Bedrock or an OAuth token for Google Vertex AI is added. An
+    /// error aborts the request with the returned message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+    ///
+    /// # fn example() -> Result<(), MessageError> {
+    /// let token = std::env::var("GCP_ACCESS_TOKEN").unwrap();
+    /// let client = AnthropicClient::vertex("my-project", "us-east5")
+    ///     .with_auth_header_fn(move |_request| {
+    ///         let mut headers = HeaderMap::new();
+    ///         let value = HeaderValue::from_str(&format!("Bearer {token}"))
+    ///             .map_err(|e| e.to_string())?;
+    ///         headers.insert(AUTHORIZATION, value);
+    ///         Ok(headers)
+    ///     })
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auth_header_fn<F>(mut self, auth_header_fn: F) -> Self
+    where
+        F: Fn(&reqwest::Request) -> Result<HeaderMap, String> + Send + Sync + 'static,
+    {
+        self.auth_header_fn = Some(Arc::new(auth_header_fn));
+        self
+    }
+
+    /// Sets a hook called with every request right before it is sent
+    ///
+    /// The hook sees the final request, authentication headers included, and
+    /// is called again for each retry. Use it for logging or per-endpoint
+    /// metrics.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// # fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_request_inspector(|request| {
+    ///         println!("{} {}", request.method(), request.url().path());
+    ///     })
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_request_inspector<F>(mut self, request_inspector: F) -> Self
+    where
+        F: Fn(&reqwest::Request) + Send + Sync + 'static,
+    {
+        self.request_inspector = Some(Arc::new(request_inspector));
+        self
+    }
+
+    /// Sets a hook called with the status and headers of every response,
+    /// before its body is read
+    ///
+    /// The hook is called for each attempt, including the responses that are
+    /// retried, and whatever the status. Use it for logging, metrics, or to
+    /// check headers before the body is parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// # fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_response_inspector(|status, headers| {
+    ///         println!("{status} (request id {:?})", headers.get("request-id"));
+    ///     })
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_response_inspector<F>(mut self, response_inspector: F) -> Self
+    where
+        F: Fn(&StatusCode, &HeaderMap) + Send + Sync + 'static,
+    {
+        self.response_inspector = Some(Arc::new(response_inspector));
+        self
+    }
+
+    /// Limits the size of the response bodies read into memory
+    ///
+    /// Reading a body stops with a `ResponseTooLarge` error as soon as it
+    /// grows past `max_response_bytes`, after decompression. Streaming
+    /// responses and downloads written to a writer are not buffered, and not
+    /// limited. By default, bodies are not limited.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = Some(max_response_bytes);
+        self
+    }
+
+    /// Uses a custom DNS resolver for all lookups
+    ///
+    /// Domains set with `with_resolve` still take precedence.
+    ///
+    /// Ignored when a custom HTTP client is set with `with_http_client`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_dns_resolver<R: Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.dns_resolver = Some(resolver);
+        self
+    }
+
     /// Builds the AnthropicClient with the specified configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the API version is not a valid header value, or if
+    /// the HTTP client cannot be created.
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::model::ModelError;
+    ///
+    /// let result = AnthropicClient::builder("your-api-key", "2023-06-01\n").build::<ModelError>();
+    /// assert!(result.is_err());
+    /// ```
     pub fn build<E>(self) -> Result<AnthropicClient, E>
     where
         E: StdError + From<String>,
     {
+        if HeaderValue::from_str(&self.api_version).is_err() {
+            return Err(E::from(format!(
+                "Invalid API version {:?}: it must be a valid header value",
+                self.api_version
+            )));
+        }
+
         // Use provided client or create a new one
         let client = if let Some(client) = self.client {
             client
         } else {
-            ReqwestClient::builder()
-                .user_agent(AnthropicClient::DEFAULT_USER_AGENT)
-                .build()
-                .map_err(|e| E::from(e.to_string()))?
+            let user_agent = self
+                .user_agent
+                .as_deref()
+                .unwrap_or(AnthropicClient::DEFAULT_USER_AGENT);
+            let builder = ReqwestClient::builder().user_agent(user_agent);
+            #[cfg(not(target_arch = "wasm32"))]
+            let builder = self.apply_native_options(builder);
+            builder.build().map_err(|e| E::from(e.to_string()))?
         };
 
         Ok(AnthropicClient {
@@ -110,8 +670,242 @@ impl AnthropicClientBuilder {
             api_key: self.api_key,
             api_version: self.api_version,
             api_base_url: self.api_base_url,
+            max_retries: self.max_retries,
+            max_retry_delay: self.max_retry_delay,
+            count_tokens_cache: self
+                .count_tokens_cache
+                .map(|(capacity, ttl)| Arc::new(Mutex::new(TokenCountCache::new(capacity, ttl)))),
+            last_rate_limit: Arc::new(Mutex::new(None)),
+            betas: self.betas,
+            default_headers: self.default_headers,
+            rate_limiter: self
+                .rate_limit
+                .map(|requests_per_minute| Arc::new(RateLimiter::new(requests_per_minute))),
+            idempotency_keys: self.idempotency_keys,
+            version_header: self.version_header,
+            platform: self.platform,
+            auth_header_fn: self.auth_header_fn,
+            request_inspector: self.request_inspector,
+            response_inspector: self.response_inspector,
+            max_response_bytes: self.max_response_bytes,
         })
     }
+
+    /// Applies the options that only native HTTP clients support
+    #[cfg(not(target_arch = "wasm32"))]
+    fn apply_native_options(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(local_address) = self.local_address {
+            builder = builder.local_address(local_address);
+        }
+        for proxy in &self.proxies {
+            builder = builder.proxy(proxy.clone());
+        }
+        for (domain, addrs) in &self.dns_overrides {
+            builder = builder.resolve_to_addrs(domain, addrs);
+        }
+        if let Some(resolver) = &self.dns_resolver {
+            builder = builder.dns_resolver(Arc::new(SharedResolver(resolver.clone())));
+        }
+        builder
+    }
+}
+
+/// Body of a request, kept so that it can be resent on retries
+pub(crate) enum RequestBody {
+    /// Serialized JSON
+    Json(Vec<u8>),
+    /// A file, sent as the `file` field of a multipart form
+    File {
+        data: Bytes,
+        filename: String,
+        media_type: String,
+    },
+}
+
+impl RequestBody {
+    /// Sets the body, and its content type, of a request
+    fn apply(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, reqwest::Error> {
+        match self {
+            RequestBody::Json(json) => Ok(request
+                .header(CONTENT_TYPE, "application/json")
+                .body(json.clone())),
+            RequestBody::File {
+                data,
+                filename,
+                media_type,
+            } => {
+                #[cfg(not(target_arch = "wasm32"))]
+                let part = Part::stream_with_length(data.clone(), data.len() as u64);
+                #[cfg(target_arch = "wasm32")]
+                let part = Part::stream(data.clone());
+                let part = part.file_name(filename.clone()).mime_str(media_type)?;
+                Ok(request.multipart(Form::new().part("file", part)))
+            }
+        }
+    }
+}
+
+/// Adapts a type-erased resolver to reqwest's `dns_resolver`
+#[cfg(not(target_arch = "wasm32"))]
+struct SharedResolver(Arc<dyn Resolve>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Resolve for SharedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        self.0.resolve(name)
+    }
+}
+
+/// Options applied to a single request on top of the client defaults
+///
+/// Passed to the `*_with_options` variants of the client trait methods.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anthropic_ai_sdk::client::{AnthropicClient, RequestOptions};
+/// use anthropic_ai_sdk::types::model::{ModelClient, ModelError};
+/// use reqwest::header::{HeaderName, HeaderValue};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), ModelError> {
+/// let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+///
+/// let options = RequestOptions::new()
+///     .with_header(
+///         HeaderName::from_static("x-tenant-id"),
+///         HeaderValue::from_static("tenant-1"),
+///     )
+///     .with_timeout(Duration::from_secs(10));
+/// let models = client.list_models_with_options(None, &options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Additional headers, overriding client defaults with the same name
+    pub headers: HeaderMap,
+    /// Timeout for this request, overriding the client timeout
+    pub timeout: Option<Duration>,
+    /// Beta features enabled for this request, on top of the client's
+    pub betas: Vec<String>,
+    /// API version of this request, overriding the client's
+    pub api_version: Option<String>,
+    /// Idempotency key of this request, resent with each of its retries
+    pub idempotency_key: Option<String>,
+}
+
+impl RequestOptions {
+    /// Create a new RequestOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a header to the request
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Set the timeout of the request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enable a beta feature for the request
+    pub fn with_beta(mut self, feature: impl Into<String>) -> Self {
+        self.betas.push(feature.into());
+        self
+    }
+
+    /// Set the API version of the request, such as a newer dated version
+    pub fn with_api_version(mut self, api_version: impl Into<String>) -> Self {
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Set the idempotency key of the request
+    ///
+    /// The key is sent in the `idempotency-key` header, whether or not the
+    /// client generates keys.
+    pub fn with_idempotency_key(mut self, idempotency_key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(idempotency_key.into());
+        self
+    }
+
+    /// Returns whether the options change what is sent, and thus possibly the
+    /// response, rather than only how the request is sent
+    pub(crate) fn alters_request(&self) -> bool {
+        !self.headers.is_empty() || !self.betas.is_empty() || self.api_version.is_some()
+    }
+
+    /// Applies the options to a request
+    pub(crate) fn apply(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if !self.headers.is_empty() {
+            request = request.headers(self.headers.clone());
+        }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+}
+
+/// Rate limit state reported by the API
+///
+/// Parsed from the `anthropic-ratelimit-*` headers sent with every response.
+/// A field is `None` when its header is missing or malformed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Maximum number of requests allowed in the current period
+    pub requests_limit: Option<u64>,
+    /// Number of requests left before being rate limited
+    pub requests_remaining: Option<u64>,
+    /// When the request limit will be fully replenished
+    pub requests_reset: Option<OffsetDateTime>,
+    /// Maximum number of tokens allowed in the current period
+    pub tokens_limit: Option<u64>,
+    /// Number of tokens left before being rate limited
+    pub tokens_remaining: Option<u64>,
+    /// When the token limit will be fully replenished
+    pub tokens_reset: Option<OffsetDateTime>,
+}
+
+impl RateLimitInfo {
+    /// Parses the rate limit headers of a response
+    ///
+    /// Returns `None` if the response has none of them.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(format!("anthropic-ratelimit-{}", name))?
+                .to_str()
+                .ok()
+                .map(str::trim)
+        };
+        let count = |name: &str| header(name)?.parse::<u64>().ok();
+        let reset = |name: &str| OffsetDateTime::parse(header(name)?, &Rfc3339).ok();
+
+        let info = Self {
+            requests_limit: count("requests-limit"),
+            requests_remaining: count("requests-remaining"),
+            requests_reset: reset("requests-reset"),
+            tokens_limit: count("tokens-limit"),
+            tokens_remaining: count("tokens-remaining"),
+            tokens_reset: reset("tokens-reset"),
+        };
+        (info != Self::default()).then_some(info)
+    }
 }
 
 impl AnthropicClient {
@@ -127,6 +921,22 @@ impl AnthropicClient {
     pub const DEFAULT_USER_AGENT: &'static str =
         concat!(env!("CARGO_PKG_NAME"), "-", env!("CARGO_PKG_VERSION"));
 
+    /// Default number of retries (retries are opt-in)
+    pub const DEFAULT_MAX_RETRIES: u32 = 0;
+
+    /// Default upper bound for the delay between two attempts
+    pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+    /// Header carrying the idempotency key of a request
+    pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+    /// Header carrying the ID the API assigns to each request, to quote when
+    /// reporting an issue
+    pub const REQUEST_ID_HEADER: &str = "request-id";
+
+    /// Delay before the first retry when the server does not send `retry-after`
+    const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
     pub fn get_client(&self) -> &ReqwestClient {
         &self.client
     }
@@ -143,6 +953,175 @@ impl AnthropicClient {
         &self.api_base_url
     }
 
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the platform the requests are sent to
+    pub fn get_platform(&self) -> &Platform {
+        &self.platform
+    }
+
+    pub(crate) fn get_count_tokens_cache(&self) -> Option<&Mutex<TokenCountCache>> {
+        self.count_tokens_cache.as_deref()
+    }
+
+    /// Returns the rate limit state reported by the last response
+    ///
+    /// Updated by every request that gets a response carrying the
+    /// `anthropic-ratelimit-*` headers, including error responses, and shared
+    /// by every clone of this client. Returns `None` until such a response has
+    /// been received.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::model::{ModelClient, ModelError};
+    /// # async fn example() -> Result<(), ModelError> {
+    /// let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+    /// client.list_models(None).await?;
+    ///
+    /// if let Some(rate_limit) = client.last_rate_limit() {
+    ///     if rate_limit.tokens_remaining.is_some_and(|tokens| tokens < 1000) {
+    ///         // slow down before hitting a 429
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        self.last_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Starts a request with the default, authentication, version and beta
+    /// headers
+    ///
+    /// Only the betas of `options` are added here. Callers apply the rest of
+    /// `options` once the request is complete, so that its headers override
+    /// the defaults.
+    fn request_builder(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        options: &RequestOptions,
+    ) -> reqwest::RequestBuilder {
+        let mut headers = self.default_headers.clone();
+        headers.remove("x-api-key");
+        headers.remove("anthropic-version");
+        let mut request = self.client.request(method, url).headers(headers);
+
+        // Other platforms take the version in the body and authenticate
+        // through `auth_header_fn`
+        if self.platform == Platform::Anthropic {
+            if self.version_header || options.api_version.is_some() {
+                request = request.header("anthropic-version", self.api_version(options));
+            }
+
+            // A sensitive value is printed as `Sensitive` by the `Debug` impls
+            // of the request and its headers
+            request = match HeaderValue::from_str(&self.api_key) {
+                Ok(mut api_key) => {
+                    api_key.set_sensitive(true);
+                    request.header("x-api-key", api_key)
+                }
+                // Let reqwest report the invalid key when the request is sent
+                Err(_) => request.header("x-api-key", &self.api_key),
+            };
+        }
+
+        // Bedrock takes the beta features in the body
+        let betas = self.betas(options);
+        if !betas.is_empty() && !matches!(self.platform, Platform::Bedrock { .. }) {
+            request = request.header("anthropic-beta", betas.join(","));
+        }
+
+        request
+    }
+
+    /// Returns the API version of a request
+    pub(crate) fn api_version<'a>(&'a self, options: &'a RequestOptions) -> &'a str {
+        options.api_version.as_deref().unwrap_or(&self.api_version)
+    }
+
+    /// Returns the beta features enabled for a request
+    pub(crate) fn betas<'a>(&'a self, options: &'a RequestOptions) -> Vec<&'a str> {
+        self.betas
+            .iter()
+            .chain(&options.betas)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Builds a request and adds the headers of the authentication hook, if
+    /// there is one
+    fn authenticate<E>(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Request, E>
+    where
+        E: From<reqwest::Error> + From<String>,
+    {
+        let mut request = request.build()?;
+        if let Some(auth_header_fn) = &self.auth_header_fn {
+            let headers = auth_header_fn(&request)
+                .map_err(|e| E::from(format!("Failed to authenticate the request: {}", e)))?;
+            request.headers_mut().extend(headers);
+        }
+        Ok(request)
+    }
+
+    /// Returns the idempotency key to send with every attempt of a request
+    ///
+    /// This is the key set in the options, or else a new UUID if the client
+    /// generates keys.
+    pub(crate) fn idempotency_key(&self, options: &RequestOptions) -> Option<String> {
+        options.idempotency_key.clone().or_else(|| {
+            self.idempotency_keys
+                .then(|| uuid::Uuid::new_v4().to_string())
+        })
+    }
+
+    /// Waits for a permit of the client-side rate limiter, if there is one
+    pub(crate) async fn acquire_rate_limit(&self) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+    }
+
+    /// Records the rate limit headers of a response, if it has any
+    pub(crate) fn record_rate_limit(&self, headers: &HeaderMap) {
+        if let Some(info) = RateLimitInfo::from_headers(headers) {
+            *self
+                .last_rate_limit
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(info);
+        }
+    }
+
+    /// Shuts the client down, closing its idle keep-alive connections
+    ///
+    /// The connection pool belongs to the underlying `reqwest::Client` and is
+    /// shared by every clone of this client (including one passed through
+    /// `with_http_client`). The pool, and with it every idle connection, is
+    /// closed as soon as the last clone is shut down or dropped, so call this on
+    /// every clone to make sure graceful shutdown does not wait on keep-alive
+    /// connections. Requests made through a client created afterwards always
+    /// open fresh connections.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::model::ModelError;
+    /// let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01").unwrap();
+    /// // ... serve requests ...
+    /// client.shutdown();
+    /// ```
+    pub fn shutdown(self) {
+        drop(self);
+    }
+
     /// Creates a new AnthropicClient builder
     pub fn builder(
         api_key: impl Into<String>,
@@ -151,6 +1130,66 @@ impl AnthropicClient {
         AnthropicClientBuilder::new(api_key, api_version)
     }
 
+    /// Creates a builder for a client sending its messages to Amazon Bedrock
+    ///
+    /// Messages are sent to the `invoke` endpoint of the model, with the
+    /// `bedrock-2023-05-31` API version. Requests are not signed: add the SigV4
+    /// signature with `AnthropicClientBuilder::with_auth_header_fn`.
+    ///
+    /// Only `create_message` is adapted to Bedrock for now. Streaming, token
+    /// counting and the other APIs are not available there through this
+    /// client.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The AWS region, such as `us-east-1`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anthropic_ai_sdk::client::AnthropicClient;
+    /// # use anthropic_ai_sdk::types::message::MessageError;
+    /// # fn sign(request: &reqwest::Request) -> Result<reqwest::header::HeaderMap, String> {
+    /// #     unimplemented!()
+    /// # }
+    /// # fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::bedrock("us-east-1")
+    ///     .with_auth_header_fn(sign)
+    ///     .build::<MessageError>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bedrock(region: impl Into<String>) -> AnthropicClientBuilder {
+        AnthropicClientBuilder::new("", Platform::BEDROCK_API_VERSION).with_platform(
+            Platform::Bedrock {
+                region: region.into(),
+            },
+        )
+    }
+
+    /// Creates a builder for a client sending its messages to Google Vertex AI
+    ///
+    /// Messages are sent to the `rawPredict` and `streamRawPredict` endpoints
+    /// of the model, with the `vertex-2023-10-16` API version. Requests are
+    /// not authenticated: add an OAuth token with
+    /// `AnthropicClientBuilder::with_auth_header_fn`.
+    ///
+    /// Only `create_message` and `create_message_streaming` are adapted to
+    /// Vertex AI for now.
+    ///
+    /// # Arguments
+    ///
+    /// * `project` - The Google Cloud project ID
+    /// * `region` - The Google Cloud region, such as `us-east5`, or `global`
+    pub fn vertex(project: impl Into<String>, region: impl Into<String>) -> AnthropicClientBuilder {
+        AnthropicClientBuilder::new("", Platform::VERTEX_API_VERSION).with_platform(
+            Platform::Vertex {
+                project: project.into(),
+                region: region.into(),
+            },
+        )
+    }
+
     /// Creates a new Anthropic API client with the specified credentials
     ///
     /// # Arguments
@@ -198,7 +1237,7 @@ impl AnthropicClient {
     ///
     /// ```no_run
     /// # use anthropic_ai_sdk::client::AnthropicClient;
-    /// # use anthropic_ai_sdk::types::admin::AdminError;
+    /// # use anthropic_ai_sdk::types::admin::api_keys::AdminError;
     /// let client = AnthropicClient::new_admin::<AdminError>(
     ///     "your-admin-api-key",
     ///     "2023-06-01",
@@ -214,6 +1253,83 @@ impl AnthropicClient {
         Self::builder(admin_api_key, api_version).build()
     }
 
+    /// Sends a request to any endpoint of the API and returns the raw response
+    ///
+    /// This is an escape hatch for endpoints and fields the SDK does not
+    /// support yet. The request carries the same authentication, version, beta
+    /// and default headers as any other, and is retried and rate limited the
+    /// same way. The response is returned whatever its status, with its body
+    /// left unparsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The HTTP method to use for the request
+    /// * `path` - The API endpoint path (will be appended to the base URL)
+    /// * `query` - Optional query parameters to include in the URL
+    /// * `body` - Optional request body, sent as JSON
+    /// * `options` - Per-request options applied on top of the client defaults
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails to send, or the body cannot be
+    /// read or is larger than `with_max_response_bytes`. Non-success statuses
+    /// are not errors.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::{AnthropicClient, RequestOptions};
+    /// use anthropic_ai_sdk::types::model::ModelError;
+    /// use reqwest::Method;
+    ///
+    /// # async fn example() -> Result<(), ModelError> {
+    /// let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+    /// let response = client
+    ///     .request_raw::<(), (), ModelError>(
+    ///         Method::GET,
+    ///         "/models/claude-3-5-sonnet-latest",
+    ///         None,
+    ///         None,
+    ///         &RequestOptions::default(),
+    ///     )
+    ///     .await?;
+    /// if response.status.is_success() {
+    ///     let model: serde_json::Value = response.json()?;
+    ///     println!("{}", model["display_name"]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_raw<Q, B, E>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<RawResponse, E>
+    where
+        Q: Serialize + ?Sized,
+        B: Serialize + ?Sized,
+        E: StdError
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
+    {
+        let response = self
+            .send::<Q, B, E>(method, path, query, body, options)
+            .await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = self.read_body::<E>(response).await?;
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
     /// Sends a request to the Anthropic API with the specified parameters
     ///
     /// # Type Parameters
@@ -221,7 +1337,7 @@ impl AnthropicClient {
     /// * `T` - The expected response type that can be deserialized from JSON
     /// * `Q` - The query parameters type that can be serialized
     /// * `B` - The request body type that can be serialized
-    /// * `E` - The error type that can be created from a string
+    /// * `E` - The error type that can be created from a string or an API error response
     ///
     /// # Arguments
     ///
@@ -229,6 +1345,7 @@ impl AnthropicClient {
     /// * `path` - The API endpoint path (will be appended to the base URL)
     /// * `query` - Optional query parameters to include in the URL
     /// * `body` - Optional request body to send
+    /// * `options` - Per-request options applied on top of the client defaults
     ///
     /// # Returns
     ///
@@ -242,20 +1359,258 @@ impl AnthropicClient {
         path: &str,
         query: Option<&Q>,
         body: Option<&B>,
+        options: &RequestOptions,
     ) -> Result<T, E>
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
         B: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError
+            + From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
+    {
+        let response = self
+            .send::<Q, B, E>(method, path, query, body, options)
+            .await?;
+        self.parse_response(response).await
+    }
+
+    /// Sends a POST request with a body that is not JSON, such as a file
+    /// upload, and parses the JSON response
+    pub(crate) async fn post_body<T, E>(
+        &self,
+        path: &str,
+        body: &RequestBody,
+        options: &RequestOptions,
+    ) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        E: StdError
+            + From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
+    {
+        let response = self
+            .send_body::<(), E>(reqwest::Method::POST, path, None, Some(body), options)
+            .await?;
+        self.parse_response(response).await
+    }
+
+    /// Parses a successful response as JSON, or turns a failed one into an
+    /// API error
+    async fn parse_response<T, E>(&self, response: reqwest::Response) -> Result<T, E>
+    where
+        T: DeserializeOwned,
+        E: From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>,
+    {
+        let status = response.status();
+        // JSON is parsed from the raw bytes, without an intermediate `String`
+        let body = self.read_body::<E>(response).await?;
+
+        if !status.is_success() {
+            let error_text = String::from_utf8_lossy(&body).into_owned();
+            return Err(E::from(ApiErrorResponse::new(status, error_text)));
+        }
+
+        // Parse the JSON response
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Reads the whole body of a response, failing as soon as it grows past
+    /// `max_response_bytes`
+    pub(crate) async fn read_body<E>(&self, response: reqwest::Response) -> Result<Bytes, E>
+    where
+        E: From<reqwest::Error> + From<ResponseTooLarge>,
+    {
+        let Some(limit) = self.max_response_bytes else {
+            return Ok(response.bytes().await?);
+        };
+        if response
+            .content_length()
+            .is_some_and(|length| length > limit as u64)
+        {
+            return Err(E::from(ResponseTooLarge { limit }));
+        }
+        let mut body = Vec::new();
+        let mut chunks = pin!(response.bytes_stream());
+        while let Some(chunk) = chunks.next().await.transpose()? {
+            if body.len() + chunk.len() > limit {
+                return Err(E::from(ResponseTooLarge { limit }));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body.into())
+    }
+
+    /// Sends a request, retrying it as configured, and returns the final
+    /// response whatever its status
+    async fn send<Q, B, E>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, E>
+    where
+        Q: Serialize + ?Sized,
+        B: Serialize + ?Sized,
+        E: StdError + From<reqwest::Error> + From<serde_json::Error> + From<String>,
+    {
+        // Serialize the body once so that it can be resent on retries
+        let body = body
+            .map(serde_json::to_vec)
+            .transpose()?
+            .map(RequestBody::Json);
+        self.send_body::<Q, E>(method, path, query, body.as_ref(), options)
+            .await
+    }
+
+    /// Sends a request with an already prepared body to a path of the API,
+    /// retrying it as configured, and returns the final response whatever its
+    /// status
+    async fn send_body<Q, E>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&RequestBody>,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, E>
+    where
+        Q: Serialize + ?Sized,
+        E: StdError + From<reqwest::Error> + From<String>,
     {
         let url = format!("{}{}", self.api_base_url, path);
+        self.send_to_url::<Q, E>(method, &url, query, body, options)
+            .await
+    }
+
+    /// Sends a request to an absolute URL, retrying it as configured, and
+    /// returns the final response whatever its status
+    ///
+    /// Every request of the client goes through here, including streaming
+    /// ones and the download of batch results from their `results_url`, so
+    /// that they share the retry, rate limiting and header settings. Only the
+    /// status is retried: the body of the final response is left to the
+    /// caller, unread.
+    ///
+    /// Each call is traced in a `debug` span recording the method, the path,
+    /// the final status, the `request-id` of the response and the number of
+    /// attempts. Headers, query and body are not recorded.
+    #[tracing::instrument(
+        name = "anthropic_request",
+        level = "debug",
+        skip_all,
+        fields(
+            http.method = %method,
+            http.path = tracing::field::Empty,
+            http.status_code = tracing::field::Empty,
+            request_id = tracing::field::Empty,
+            attempts = tracing::field::Empty,
+        )
+    )]
+    pub(crate) async fn send_to_url<Q, E>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: Option<&Q>,
+        body: Option<&RequestBody>,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, E>
+    where
+        Q: Serialize + ?Sized,
+        E: StdError + From<reqwest::Error> + From<String>,
+    {
+        let span = tracing::Span::current();
+        if let Ok(url) = reqwest::Url::parse(url) {
+            span.record("http.path", url.path());
+        }
+        let idempotency_key = self.idempotency_key(options);
+
+        let mut attempt = 0;
+        let response = loop {
+            let request = self.authorized_request::<Q, E>(
+                method.clone(),
+                url,
+                query,
+                body,
+                idempotency_key.as_deref(),
+                options,
+            )?;
 
-        let mut request = self
-            .client
-            .request(method, &url)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", &self.api_version);
+            let can_retry = attempt < self.max_retries;
+            self.acquire_rate_limit().await;
+            if let Some(request_inspector) = &self.request_inspector {
+                request_inspector(&request);
+            }
+            let result = self.client.execute(request).await;
+            if let (Some(response_inspector), Ok(response)) = (&self.response_inspector, &result) {
+                response_inspector(&response.status(), response.headers());
+            }
+            match result {
+                Ok(response)
+                    if can_retry && ApiErrorResponse::is_retryable_status(response.status()) =>
+                {
+                    let delay = self.retry_delay(attempt, response.headers());
+                    tracing::debug!(status = response.status().as_u16(), ?delay, "retrying");
+                    timer::sleep(delay).await;
+                }
+                Ok(response) => break response,
+                Err(e) if can_retry && ApiErrorResponse::is_retryable_error(&e) => {
+                    let delay = self.retry_delay(attempt, &HeaderMap::new());
+                    tracing::debug!(error = %e, ?delay, "retrying");
+                    timer::sleep(delay).await;
+                }
+                Err(e) => return Err(E::from(e)),
+            }
+            attempt += 1;
+        };
+
+        span.record("http.status_code", response.status().as_u16());
+        span.record("attempts", attempt + 1);
+        if let Some(request_id) = response
+            .headers()
+            .get(Self::REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            span.record("request_id", request_id);
+        }
+        self.record_rate_limit(response.headers());
+        Ok(response)
+    }
+
+    /// Builds a request with all the headers of the client and of `options`,
+    /// then authenticates it
+    ///
+    /// The body is rebuilt from `body` each time, so that the request can be
+    /// sent again on retries.
+    pub(crate) fn authorized_request<Q, E>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        query: Option<&Q>,
+        body: Option<&RequestBody>,
+        idempotency_key: Option<&str>,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Request, E>
+    where
+        Q: Serialize + ?Sized,
+        E: From<reqwest::Error> + From<String>,
+    {
+        let mut request = self.request_builder(method, url, options);
+
+        if let Some(key) = idempotency_key {
+            request = request.header(Self::IDEMPOTENCY_KEY_HEADER, key);
+        }
 
         // Add query parameters if provided
         if let Some(q) = query {
@@ -264,30 +1619,44 @@ impl AnthropicClient {
 
         // Add request body if provided
         if let Some(b) = body {
-            let _json = serde_json::to_string_pretty(b)
-                .map_err(|e| E::from(format!("Failed to serialize body: {}", e)))?;
-            request = request.json(b);
+            request = b.apply(request)?;
         }
 
-        let response = request.send().await.map_err(|e| E::from(e.to_string()))?;
+        self.authenticate::<E>(options.apply(request))
+    }
 
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .map_err(|e| E::from(format!("Failed to get response body: {}", e)))?;
+    /// Computes how long to wait before retrying the given attempt
+    ///
+    /// The delay requested by the server through `retry-after` wins over the
+    /// exponential backoff, and both are capped at `max_retry_delay`.
+    fn retry_delay(&self, attempt: u32, headers: &HeaderMap) -> Duration {
+        Self::parse_retry_after(headers)
+            .unwrap_or_else(|| {
+                Self::INITIAL_RETRY_DELAY.saturating_mul(2u32.saturating_pow(attempt))
+            })
+            .min(self.max_retry_delay)
+    }
 
-        if !status.is_success() {
-            return Err(E::from(body));
+    /// Parses the `retry-after-ms` and `retry-after` headers
+    ///
+    /// `retry-after` may contain either a number of seconds or an HTTP date.
+    fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+        let header = |name: &str| headers.get(name)?.to_str().ok().map(str::trim);
+        let from_secs = |secs: f64| {
+            (secs.is_finite() && secs >= 0.0)
+                .then(|| Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX))
+        };
+
+        if let Some(millis) = header("retry-after-ms").and_then(|v| v.parse::<f64>().ok()) {
+            return from_secs(millis / 1000.0);
         }
 
-        // Parse the JSON response
-        serde_json::from_str(&body).map_err(|e| {
-            E::from(format!(
-                "JSON parsing error: {}. Response body: {}",
-                e, body
-            ))
-        })
+        let value = header("retry-after")?;
+        if let Ok(secs) = value.parse::<f64>() {
+            return from_secs(secs);
+        }
+        let date = httpdate::parse_http_date(value).ok()?;
+        Some(date.duration_since(timer::now()).unwrap_or(Duration::ZERO))
     }
 
     /// Sends a GET request to the specified endpoint
@@ -302,13 +1671,24 @@ impl AnthropicClient {
     ///
     /// * `path` - The API endpoint path
     /// * `query` - Optional query parameters
-    pub(crate) async fn get<T, Q, E>(&self, path: &str, query: Option<&Q>) -> Result<T, E>
+    /// * `options` - Per-request options
+    pub(crate) async fn get<T, Q, E>(
+        &self,
+        path: &str,
+        query: Option<&Q>,
+        options: &RequestOptions,
+    ) -> Result<T, E>
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError
+            + From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
     {
-        self.send_request::<T, Q, (), E>(reqwest::Method::GET, path, query, None)
+        self.send_request::<T, Q, (), E>(reqwest::Method::GET, path, query, None, options)
             .await
     }
 
@@ -324,13 +1704,24 @@ impl AnthropicClient {
     ///
     /// * `path` - The API endpoint path
     /// * `body` - Optional request body
-    pub(crate) async fn post<T, B, E>(&self, path: &str, body: Option<&B>) -> Result<T, E>
+    /// * `options` - Per-request options
+    pub(crate) async fn post<T, B, E>(
+        &self,
+        path: &str,
+        body: Option<&B>,
+        options: &RequestOptions,
+    ) -> Result<T, E>
     where
         T: DeserializeOwned,
         B: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError
+            + From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
     {
-        self.send_request::<T, (), B, E>(reqwest::Method::POST, path, None, body)
+        self.send_request::<T, (), B, E>(reqwest::Method::POST, path, None, body, options)
             .await
     }
 
@@ -346,13 +1737,24 @@ impl AnthropicClient {
     ///
     /// * `path` - The API endpoint path
     /// * `query` - Optional query parameters
-    pub(crate) async fn delete<T, Q, E>(&self, path: &str, query: Option<&Q>) -> Result<T, E>
+    /// * `options` - Per-request options
+    pub(crate) async fn delete<T, Q, E>(
+        &self,
+        path: &str,
+        query: Option<&Q>,
+        options: &RequestOptions,
+    ) -> Result<T, E>
     where
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
-        E: StdError + From<String>,
+        E: StdError
+            + From<ApiErrorResponse>
+            + From<reqwest::Error>
+            + From<serde_json::Error>
+            + From<ResponseTooLarge>
+            + From<String>,
     {
-        self.send_request::<T, Q, (), E>(reqwest::Method::DELETE, path, query, None)
+        self.send_request::<T, Q, (), E>(reqwest::Method::DELETE, path, query, None, options)
             .await
     }
 }
@@ -0,0 +1,238 @@
+//! Files API
+//!
+//! This module contains the implementations for the Anthropic Files API endpoints.
+//! It provides functionality for uploading files once and referencing them by ID
+//! in the content blocks of later messages.
+
+use crate::client::{AnthropicClient, RequestBody, RequestOptions};
+use crate::pagination::{Cursor, Page, paginate};
+use crate::types::error::ApiErrorResponse;
+use crate::types::files::{
+    FILES_API_BETA, FileDeleted, FileError, FileMetadata, FilesClient, ListFilesParams,
+    ListFilesResponse,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+
+/// Adds the beta header the Files API requires, unless it is already set
+fn files_options(options: &RequestOptions) -> RequestOptions {
+    let mut options = options.clone();
+    if !options.betas.iter().any(|beta| beta == FILES_API_BETA) {
+        options.betas.push(FILES_API_BETA.to_string());
+    }
+    options
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FilesClient for AnthropicClient {
+    /// Upload a file
+    ///
+    /// Uploads a file that can then be referenced by its ID, for example with
+    /// `ContentBlock::document_file`, instead of being sent with every message.
+    /// Messages referencing files must enable the `files-api-2025-04-14` beta.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Content of the file
+    /// * `filename` - Name of the file
+    /// * `media_type` - Media type of the file, such as `application/pdf`
+    ///
+    /// # Returns
+    ///
+    /// Returns the metadata of the uploaded file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The media type is invalid
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::files::{FileError, FilesClient};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<FileError>("your-api-key", "2023-06-01")?;
+    /// let data = std::fs::read("contract.pdf")?;
+    /// let file = client
+    ///     .upload_file(data.into(), "contract.pdf", "application/pdf")
+    ///     .await?;
+    /// println!("Uploaded: {}", file.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn upload_file<'a>(
+        &'a self,
+        data: Bytes,
+        filename: &'a str,
+        media_type: &'a str,
+    ) -> Result<FileMetadata, FileError> {
+        self.upload_file_with_options(data, filename, media_type, &RequestOptions::default())
+            .await
+    }
+
+    /// List files
+    ///
+    /// Lists the files uploaded in the workspace, most recent first
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The response cannot be parsed
+    async fn list_files<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> Result<ListFilesResponse, FileError> {
+        self.list_files_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    fn list_files_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> impl Stream<Item = Result<FileMetadata, FileError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_files(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Get the metadata of a file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The request fails to send
+    /// - The API returns an error response, such as `FileError::NotFound`
+    /// - The response cannot be parsed
+    async fn get_file_metadata<'a>(&'a self, file_id: &'a str) -> Result<FileMetadata, FileError> {
+        self.get_file_metadata_with_options(file_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Delete a file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The request fails to send
+    /// - The API returns an error response, such as `FileError::NotFound`
+    /// - The response cannot be parsed
+    async fn delete_file<'a>(&'a self, file_id: &'a str) -> Result<FileDeleted, FileError> {
+        self.delete_file_with_options(file_id, &RequestOptions::default())
+            .await
+    }
+
+    /// Download the content of a file
+    ///
+    /// Only files created by tools can be downloaded, see
+    /// `FileMetadata::downloadable`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FileError` if:
+    /// - The request fails to send
+    /// - The API returns an error response
+    async fn download_file<'a>(&'a self, file_id: &'a str) -> Result<Bytes, FileError> {
+        self.download_file_with_options(file_id, &RequestOptions::default())
+            .await
+    }
+
+    async fn upload_file_with_options<'a>(
+        &'a self,
+        data: Bytes,
+        filename: &'a str,
+        media_type: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileMetadata, FileError> {
+        let body = RequestBody::File {
+            data,
+            filename: filename.to_string(),
+            media_type: media_type.to_string(),
+        };
+        self.post_body("/files", &body, &files_options(options))
+            .await
+    }
+
+    async fn list_files_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListFilesResponse, FileError> {
+        self.get("/files", params, &files_options(options)).await
+    }
+
+    async fn get_file_metadata_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileMetadata, FileError> {
+        self.get(
+            &format!("/files/{}", file_id),
+            Option::<&()>::None,
+            &files_options(options),
+        )
+        .await
+    }
+
+    async fn delete_file_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileDeleted, FileError> {
+        self.delete(
+            &format!("/files/{}", file_id),
+            Option::<&()>::None,
+            &files_options(options),
+        )
+        .await
+    }
+
+    async fn download_file_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Bytes, FileError> {
+        let response = self
+            .request_raw::<(), (), FileError>(
+                reqwest::Method::GET,
+                &format!("/files/{}/content", file_id),
+                None,
+                None,
+                &files_options(options),
+            )
+            .await?;
+        if !response.status.is_success() {
+            let error_text = String::from_utf8_lossy(&response.body).into_owned();
+            return Err(FileError::from(ApiErrorResponse::new(
+                response.status,
+                error_text,
+            )));
+        }
+        Ok(response.body)
+    }
+}
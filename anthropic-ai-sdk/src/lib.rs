@@ -1,6 +1,15 @@
 pub mod admin_client;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 pub mod client;
+pub mod files;
 pub mod message_batches;
 pub mod messages;
 pub mod models;
+mod pagination;
+pub mod platform;
+pub mod prelude;
+mod rate_limit;
+mod timer;
 pub mod types;
@@ -3,17 +3,25 @@
 //! This module contains the implementations for the Anthropic Message Batches API endpoints.
 //! It provides functionality for creating message batches.
 
-use crate::client::AnthropicClient;
+use crate::client::{AnthropicClient, RequestOptions};
+use crate::pagination::{Cursor, Page, paginate};
+use crate::timer::{self, Instant};
+use crate::types::error::ApiErrorResponse;
 use crate::types::message_batches::{
     CancelMessageBatchParams, CancelResponse, CreateMessageBatchParams, DeleteMessageBatchParams,
     DeleteResponse, ListMessageBatchesParams, ListMessageBatchesResponse, MessageBatch,
-    MessageBatchClient, MessageBatchError, RetrieveMessageBatchParams,
-    RetrieveMessageBatchResponse, RetrieveMessageBatchResultsParams,
-    RetrieveMessageBatchResultsResponse,
+    MessageBatchClient, MessageBatchError, MessageBatchResult, PollConfig, ProcessingStatus,
+    RequestCounts, RetrieveMessageBatchParams, RetrieveMessageBatchResponse,
+    RetrieveMessageBatchResultsParams, RetrieveMessageBatchResultsResponse,
 };
 use async_trait::async_trait;
+use futures_util::{Stream, StreamExt, TryStreamExt, stream};
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-#[async_trait]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl MessageBatchClient for AnthropicClient {
     /// Creates a message batch
     ///
@@ -32,6 +40,9 @@ impl MessageBatchClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageBatchError` if:
+    /// - The batch has more than 100,000 requests (`MessageBatchError::BatchTooLarge`)
+    /// - The batch is larger than 256MB (`MessageBatchError::BatchSizeExceeded`)
+    /// - A message has an unknown role (`MessageBatchError::InvalidParameter`)
     /// - The request fails to send
     /// - The API returns an error response
     /// - The response cannot be parsed
@@ -68,7 +79,8 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         body: &'a CreateMessageBatchParams,
     ) -> Result<MessageBatch, MessageBatchError> {
-        self.post("/messages/batches", Some(body)).await
+        self.create_message_batch_with_options(body, &RequestOptions::default())
+            .await
     }
 
     /// List message batches
@@ -103,11 +115,8 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         params: Option<&'a ListMessageBatchesParams>,
     ) -> Result<ListMessageBatchesResponse, MessageBatchError> {
-        self.get::<ListMessageBatchesResponse, ListMessageBatchesParams, MessageBatchError>(
-            "/messages/batches",
-            params,
-        )
-        .await
+        self.list_message_batches_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Retrieve a message batch
@@ -142,11 +151,8 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         params: &'a RetrieveMessageBatchParams,
     ) -> Result<RetrieveMessageBatchResponse, MessageBatchError> {
-        self.get::<RetrieveMessageBatchResponse, RetrieveMessageBatchParams, MessageBatchError>(
-            &format!("/messages/batches/{}", params.message_batch_id),
-            None,
-        )
-        .await
+        self.retrieve_message_batch_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Retrieve message batch results
@@ -181,11 +187,8 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         params: &'a RetrieveMessageBatchResultsParams,
     ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError> {
-        self.get::<RetrieveMessageBatchResultsResponse, RetrieveMessageBatchResultsParams, MessageBatchError>(
-            &format!("/messages/batches/{}/results", params.message_batch_id),
-            None,
-        )
-        .await
+        self.retrieve_message_batch_results_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Cancel a message batch
@@ -194,7 +197,8 @@ impl MessageBatchClient for AnthropicClient {
     ///
     /// # Returns
     ///
-    /// Returns a cancel response
+    /// Returns the message batch, whose processing status is `Canceling`
+    /// until the requests in progress have finished
     ///
     /// # Errors
     ///
@@ -220,11 +224,8 @@ impl MessageBatchClient for AnthropicClient {
         &'a self,
         params: &'a CancelMessageBatchParams,
     ) -> Result<CancelResponse, MessageBatchError> {
-        self.post::<CancelResponse, CancelMessageBatchParams, MessageBatchError>(
-            &format!("/messages/batches/{}/cancel", params.message_batch_id),
-            Some(params),
-        )
-        .await
+        self.cancel_message_batch_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Delete a message batch
@@ -258,11 +259,597 @@ impl MessageBatchClient for AnthropicClient {
     async fn delete_message_batch<'a>(
         &'a self,
         params: &'a DeleteMessageBatchParams,
+    ) -> Result<DeleteResponse, MessageBatchError> {
+        self.delete_message_batch_with_options(params, &RequestOptions::default())
+            .await
+    }
+
+    async fn create_message_batch_with_options<'a>(
+        &'a self,
+        body: &'a CreateMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        body.validate()?;
+        self.post("/messages/batches", Some(body), options).await
+    }
+
+    async fn list_message_batches_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListMessageBatchesResponse, MessageBatchError> {
+        self.get::<ListMessageBatchesResponse, ListMessageBatchesParams, MessageBatchError>(
+            "/messages/batches",
+            params,
+            options,
+        )
+        .await
+    }
+
+    async fn retrieve_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<RetrieveMessageBatchResponse, MessageBatchError> {
+        self.get::<RetrieveMessageBatchResponse, RetrieveMessageBatchParams, MessageBatchError>(
+            &format!("/messages/batches/{}", params.message_batch_id),
+            None,
+            options,
+        )
+        .await
+    }
+
+    async fn retrieve_message_batch_results_with_options<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+        options: &'a RequestOptions,
+    ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError> {
+        self.stream_message_batch_results_with_options(&params.message_batch_id, options)
+            .await?
+            .try_collect()
+            .await
+    }
+
+    async fn cancel_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a CancelMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<CancelResponse, MessageBatchError> {
+        // The batch ID is part of the path; the endpoint takes no body
+        self.post::<CancelResponse, (), MessageBatchError>(
+            &format!("/messages/batches/{}/cancel", params.message_batch_id),
+            None,
+            options,
+        )
+        .await
+    }
+
+    async fn delete_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a DeleteMessageBatchParams,
+        options: &'a RequestOptions,
     ) -> Result<DeleteResponse, MessageBatchError> {
         self.delete::<DeleteResponse, DeleteMessageBatchParams, MessageBatchError>(
             &format!("/messages/batches/{}", params.message_batch_id),
             None,
+            options,
         )
         .await
     }
+
+    /// Streams the results of a message batch
+    ///
+    /// Fetches the batch to find its `results_url`, then parses the JSONL
+    /// results as they arrive, one `MessageBatchResult` per line. Results are
+    /// only available once the batch has ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_batch_id` - The ID of the message batch
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of batch results on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The batch has no results yet
+    /// - The request fails to send
+    /// - The API returns an error response
+    ///
+    /// Each item of the stream is an error if its line cannot be parsed or the
+    /// connection fails while reading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{MessageBatchClient, MessageBatchError};
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let results = client.stream_message_batch_results("msgbatch_xyz").await?;
+    /// futures_util::pin_mut!(results);
+    /// while let Some(result) = results.next().await {
+    ///     match result {
+    ///         Ok(result) => println!("{}: {:?}", result.custom_id, result.result),
+    ///         Err(e) => eprintln!("Skipping result: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn stream_message_batch_results<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+    ) -> Result<
+        impl Stream<Item = Result<MessageBatchResult, MessageBatchError>> + 'a,
+        MessageBatchError,
+    > {
+        let response = self
+            .send_results_request(message_batch_id, &RequestOptions::default())
+            .await?;
+        Ok(parse_jsonl_stream(response))
+    }
+
+    async fn stream_message_batch_results_with_options<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<
+        impl Stream<Item = Result<MessageBatchResult, MessageBatchError>> + 'a,
+        MessageBatchError,
+    > {
+        let response = self.send_results_request(message_batch_id, options).await?;
+        Ok(parse_jsonl_stream(response))
+    }
+
+    /// Writes the raw results of a message batch to a writer
+    ///
+    /// Fetches the batch to find its `results_url`, then copies the JSONL
+    /// body to `writer` chunk by chunk, so that results of any size can be
+    /// persisted without being held in memory. The writer is flushed once the
+    /// body has been copied.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_batch_id` - The ID of the message batch
+    /// * `writer` - Destination of the results, such as a `tokio::fs::File`
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of bytes written on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - The batch has no results yet
+    /// - The request fails to send
+    /// - The API returns an error response
+    /// - The connection fails while reading the body
+    /// - Writing to `writer` fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{MessageBatchClient, MessageBatchError};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let mut file = tokio::fs::File::create("results.jsonl").await?;
+    /// let written = client
+    ///     .download_batch_results_to("msgbatch_xyz", &mut file)
+    ///     .await?;
+    /// println!("Wrote {} bytes", written);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn download_batch_results_to<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        writer: &'a mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64, MessageBatchError> {
+        self.download_batch_results_to_with_options(
+            message_batch_id,
+            writer,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    async fn download_batch_results_to_with_options<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        writer: &'a mut (dyn AsyncWrite + Unpin + Send),
+        options: &'a RequestOptions,
+    ) -> Result<u64, MessageBatchError> {
+        let response = self.send_results_request(message_batch_id, options).await?;
+        let mut bytes = response.bytes_stream();
+        let mut written = 0;
+        while let Some(chunk) = bytes.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(written)
+    }
+
+    fn list_message_batches_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+    ) -> impl Stream<Item = Result<MessageBatch, MessageBatchError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_message_batches(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
+
+    /// Creates a message batch and waits until its results are available
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for creating the message batch
+    /// * `poll_interval` - Delay between two status checks
+    /// * `timeout` - How long to wait for the batch to end
+    /// * `on_progress` - Called with the batch after it is created and after each poll
+    ///
+    /// # Returns
+    ///
+    /// Returns the results of every request in the batch on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - Creating or polling the batch fails
+    /// - The batch has not ended within `timeout` (`MessageBatchError::WaitTimeout`)
+    /// - The results cannot be downloaded or parsed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, Message, MessageBatch, MessageBatchClient, MessageBatchError,
+    ///     MessageRequest, MessageRequestParams,
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let messages = vec![Message::new("user", "Hello!")];
+    /// let request = MessageRequest::new(MessageRequestParams::new("claude-3-haiku", messages, 100))
+    ///     .with_custom_id("req1");
+    /// let params = CreateMessageBatchParams::new(vec![request]);
+    ///
+    /// let progress = |batch: &MessageBatch| {
+    ///     println!("{:?}: {} processing", batch.processing_status, batch.request_counts.processing);
+    /// };
+    /// let results = client
+    ///     .create_and_wait(
+    ///         &params,
+    ///         Duration::from_secs(30),
+    ///         Duration::from_secs(24 * 60 * 60),
+    ///         Some(&progress),
+    ///     )
+    ///     .await?;
+    /// println!("{} results", results.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn create_and_wait<'a>(
+        &'a self,
+        params: &'a CreateMessageBatchParams,
+        poll_interval: Duration,
+        timeout: Duration,
+        on_progress: Option<&'a (dyn for<'b> Fn(&'b MessageBatch) + Send + Sync)>,
+    ) -> Result<Vec<MessageBatchResult>, MessageBatchError> {
+        let deadline = Instant::now() + timeout;
+        let batch = self.create_message_batch(params).await?;
+        let config = PollConfig::new()
+            .with_interval(poll_interval)
+            .with_timeout(timeout);
+        let mut on_progress = |batch: &MessageBatch| {
+            if let Some(on_progress) = on_progress {
+                on_progress(batch);
+            }
+        };
+        let batch = self
+            .poll_until_ended(batch, config, deadline, &mut on_progress)
+            .await?;
+
+        let response = self
+            .download_results(&batch, &RequestOptions::default())
+            .await?;
+        parse_jsonl_stream(response).try_collect().await
+    }
+
+    /// Waits until a message batch has ended
+    ///
+    /// # Arguments
+    ///
+    /// * `message_batch_id` - ID of the message batch to wait for
+    /// * `config` - Poll interval and timeout, see `PollConfig::default`
+    ///
+    /// # Returns
+    ///
+    /// Returns the ended message batch on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - Polling the batch fails
+    /// - The batch has not ended within the timeout (`MessageBatchError::WaitTimeout`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     MessageBatchClient, MessageBatchError, PollConfig,
+    /// };
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() -> Result<(), MessageBatchError> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let config = PollConfig::new().with_interval(Duration::from_secs(60));
+    /// let batch = client.wait_for_batch("msgbatch_123", config).await?;
+    /// println!("{} succeeded", batch.request_counts.succeeded);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn wait_for_batch<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        config: PollConfig,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        self.wait_for_batch_with_progress(message_batch_id, config, &mut |_| {})
+            .await
+    }
+
+    /// Waits until a message batch has ended, reporting its progress
+    ///
+    /// `on_progress` is called with the request counts of the batch after
+    /// each successful status check, including the first and the last one.
+    /// A status check failing with a retryable error, such as a rate limit,
+    /// is tried again at the next interval, up to `config.max_poll_failures`
+    /// times in a row.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MessageBatchError` if:
+    /// - Polling the batch fails with a non-retryable error, or more times in
+    ///   a row than allowed
+    /// - The batch has not ended within the timeout (`MessageBatchError::WaitTimeout`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     MessageBatchClient, MessageBatchError, PollConfig,
+    /// };
+    ///
+    /// # async fn example() -> Result<(), MessageBatchError> {
+    /// let client = AnthropicClient::new::<MessageBatchError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let batch = client
+    ///     .wait_for_batch_with_progress("msgbatch_123", PollConfig::new(), &mut |counts| {
+    ///         println!("{} / {} succeeded", counts.succeeded, counts.total());
+    ///     })
+    ///     .await?;
+    /// println!("Done: {:?}", batch.request_counts);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn wait_for_batch_with_progress<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        config: PollConfig,
+        on_progress: &'a mut (dyn for<'b> FnMut(&'b RequestCounts) + Send),
+    ) -> Result<MessageBatch, MessageBatchError> {
+        let deadline = Instant::now() + config.timeout;
+        let batch = self
+            .retrieve_message_batch(&RetrieveMessageBatchParams::new(message_batch_id))
+            .await?;
+        self.poll_until_ended(batch, config, deadline, &mut |batch| {
+            on_progress(&batch.request_counts)
+        })
+        .await
+    }
+}
+
+impl AnthropicClient {
+    /// Polls a message batch until it has ended or `deadline` has passed
+    ///
+    /// `on_progress` is called with the given batch, then after each
+    /// successful poll. Up to `config.max_poll_failures` retryable errors in a
+    /// row are skipped over.
+    async fn poll_until_ended(
+        &self,
+        mut batch: MessageBatch,
+        config: PollConfig,
+        deadline: Instant,
+        on_progress: &mut (dyn for<'b> FnMut(&'b MessageBatch) + Send),
+    ) -> Result<MessageBatch, MessageBatchError> {
+        on_progress(&batch);
+        let mut failures = 0;
+        loop {
+            if batch.processing_status == ProcessingStatus::Ended {
+                return Ok(batch);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(MessageBatchError::WaitTimeout {
+                    message_batch_id: batch.id,
+                });
+            }
+            timer::sleep(config.interval.min(deadline - now)).await;
+            match self
+                .retrieve_message_batch(&RetrieveMessageBatchParams::new(&batch.id))
+                .await
+            {
+                Ok(latest) => {
+                    batch = latest;
+                    failures = 0;
+                    on_progress(&batch);
+                }
+                Err(e) if e.is_retryable() && failures < config.max_poll_failures => {
+                    tracing::debug!(error = %e, "polling message batch failed, trying again");
+                    failures += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Retrieves a message batch and requests its results file
+    async fn send_results_request(
+        &self,
+        message_batch_id: &str,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, MessageBatchError> {
+        let batch = self
+            .retrieve_message_batch_with_options(
+                &RetrieveMessageBatchParams::new(message_batch_id),
+                options,
+            )
+            .await?;
+        self.download_results(&batch, options).await
+    }
+
+    /// Requests the results file of an already retrieved message batch
+    async fn download_results(
+        &self,
+        batch: &MessageBatch,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, MessageBatchError> {
+        let results_url = batch.results_url.as_deref().ok_or_else(|| {
+            MessageBatchError::ApiError(format!("Message batch {} has no results yet", batch.id))
+        })?;
+
+        let response = self
+            .send_to_url::<(), MessageBatchError>(
+                reqwest::Method::GET,
+                results_url,
+                None,
+                None,
+                options,
+            )
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = self.read_body::<MessageBatchError>(response).await?;
+            let error_text = String::from_utf8_lossy(&error_body).into_owned();
+            return Err(MessageBatchError::from(ApiErrorResponse::new(
+                status, error_text,
+            )));
+        }
+
+        Ok(response)
+    }
+}
+
+/// Parses a JSONL response body into a stream of batch results
+///
+/// Lines can span several chunks of the body, so bytes are buffered until a
+/// newline is seen. Blank lines are skipped.
+///
+/// Results files can hold tens of thousands of lines, some of them long, so
+/// the buffer is only searched past the bytes already scanned, and lines are
+/// sliced out by offset. Consumed lines are dropped once per chunk.
+fn parse_jsonl_stream(
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<MessageBatchResult, MessageBatchError>> {
+    let lines = JsonlLines {
+        bytes: Box::pin(response.bytes_stream()),
+        buffer: Vec::new(),
+        start: 0,
+        scanned: 0,
+        done: false,
+    };
+
+    stream::unfold(lines, |mut lines| async move {
+        loop {
+            let end = if let Some(pos) = lines.buffer[lines.scanned..]
+                .iter()
+                .position(|&b| b == b'\n')
+            {
+                lines.scanned + pos
+            } else if lines.done {
+                if lines.start == lines.buffer.len() {
+                    return None;
+                }
+                lines.buffer.len()
+            } else {
+                lines.buffer.drain(..lines.start);
+                lines.start = 0;
+                lines.scanned = lines.buffer.len();
+                match lines.bytes.next().await {
+                    Some(Ok(chunk)) => lines.buffer.extend_from_slice(&chunk),
+                    Some(Err(e)) => {
+                        // The body cannot be resumed, so drop what is left
+                        lines.buffer.clear();
+                        lines.scanned = 0;
+                        lines.done = true;
+                        return Some((Err(MessageBatchError::Http(e)), lines));
+                    }
+                    None => lines.done = true,
+                }
+                continue;
+            };
+
+            let line = lines.buffer[lines.start..end].trim_ascii();
+            lines.start = (end + 1).min(lines.buffer.len());
+            lines.scanned = lines.start;
+            if line.is_empty() {
+                continue;
+            }
+            let result = serde_json::from_slice::<MessageBatchResult>(line).map_err(|e| {
+                MessageBatchError::ApiError(format!(
+                    "Failed to parse batch result: {}. Line: {}",
+                    e,
+                    String::from_utf8_lossy(line)
+                ))
+            });
+            return Some((result, lines));
+        }
+    })
+}
+
+/// State of `parse_jsonl_stream` between two results
+struct JsonlLines<S> {
+    /// Chunks of the response body
+    bytes: Pin<Box<S>>,
+    /// Bytes received but not yet returned as complete lines
+    buffer: Vec<u8>,
+    /// Offset in `buffer` of the first byte of the next line
+    start: usize,
+    /// Offset in `buffer` up to which no newline was found
+    scanned: usize,
+    /// Whether the body has been fully read
+    done: bool,
 }
@@ -5,17 +5,20 @@
 
 use eventsource_stream::Eventsource;
 use futures_util::Stream;
-use reqwest::header::HeaderValue;
 
-use crate::client::AnthropicClient;
+use crate::cache::TokenCountCache;
+use crate::client::{AnthropicClient, RequestBody, RequestOptions};
+use crate::platform::Platform;
+use crate::types::error::ApiErrorResponse;
 use crate::types::message::{
     CountMessageTokensParams, CountMessageTokensResponse, CreateMessageParams,
-    CreateMessageResponse, MessageClient, MessageError, StreamEvent,
+    CreateMessageResponse, MessageClient, MessageError, MessageStream, StreamEvent,
 };
 use async_trait::async_trait;
-use futures_util::StreamExt;
+use futures_util::{StreamExt, future};
 
-#[async_trait]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl MessageClient for AnthropicClient {
     /// Creates a message using the specified model
     ///
@@ -36,8 +39,11 @@ impl MessageClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageError` if:
+    /// - The parameters are invalid (`MessageError::InvalidParameter`), see
+    ///   `CreateMessageParams::validate`
     /// - The request fails to send
     /// - The API returns an error response
+    /// - The requested model is deprecated (`MessageError::ModelDeprecated`)
     /// - The response cannot be parsed
     ///
     /// # Examples
@@ -67,7 +73,8 @@ impl MessageClient for AnthropicClient {
         &'a self,
         body: Option<&'a CreateMessageParams>,
     ) -> Result<CreateMessageResponse, MessageError> {
-        self.post("/messages", body).await
+        self.create_message_with_options(body, &RequestOptions::default())
+            .await
     }
 
     /// Counts the number of tokens in a message
@@ -86,6 +93,8 @@ impl MessageClient for AnthropicClient {
     /// # Errors
     ///
     /// Returns a `MessageError` if:
+    /// - The parameters are invalid (`MessageError::InvalidParameter`), see
+    ///   `CountMessageTokensParams::validate`
     /// - The request fails to send
     /// - The API returns an error response
     /// - The response cannot be parsed
@@ -104,7 +113,8 @@ impl MessageClient for AnthropicClient {
         &'a self,
         body: Option<&'a CountMessageTokensParams>,
     ) -> Result<CountMessageTokensResponse, MessageError> {
-        self.post("/messages/count_tokens", body).await
+        self.count_tokens_with_options(body, &RequestOptions::default())
+            .await
     }
 
     /// Creates a message with streaming enabled
@@ -118,64 +128,288 @@ impl MessageClient for AnthropicClient {
     ///
     /// Returns a stream of events on success.
     ///
+    /// # Cancellation
+    ///
+    /// The stream owns the HTTP response. Dropping it closes the connection
+    /// and aborts the generation, whether or not the stream was read to the
+    /// end. To stop a stream from elsewhere, such as when a user navigates
+    /// away, wrap it with `futures_util::stream::abortable`:
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams,
+    /// };
+    /// use futures_util::StreamExt;
+    /// use futures_util::stream::abortable;
+    ///
+    /// # async fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::new::<MessageError>("your-api-key", "2023-06-01")?;
+    /// let body = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![Message::user("Write a long story")],
+    ///     max_tokens: 4096,
+    /// })
+    /// .with_stream(true);
+    ///
+    /// let (mut stream, handle) = abortable(client.create_message_streaming(&body).await?);
+    /// // Hand `handle` to whoever decides to stop, who calls `handle.abort()`
+    /// # handle.abort();
+    /// while let Some(event) = stream.next().await {
+    ///     println!("{:?}", event?);
+    /// }
+    /// // The stream ends early once aborted, and dropping it closes the connection
+    /// drop(stream);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
     /// # Errors
     ///
     /// Returns a `MessageError` if:
+    /// - The parameters are invalid (`MessageError::InvalidParameter`), see
+    ///   `CreateMessageParams::validate`
     /// - The request fails to send
     async fn create_message_streaming<'a>(
         &'a self,
         body: &'a CreateMessageParams,
-    ) -> Result<impl Stream<Item = Result<StreamEvent, MessageError>> + 'a, MessageError> {
+    ) -> Result<MessageStream, MessageError> {
+        let response = self
+            .send_streaming_request(body, &RequestOptions::default())
+            .await?;
+        Ok(MessageStream::new(parse_sse_stream(response)))
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(model = body.map(|body| body.model.as_str()))
+    )]
+    async fn create_message_with_options<'a>(
+        &'a self,
+        body: Option<&'a CreateMessageParams>,
+        options: &'a RequestOptions,
+    ) -> Result<CreateMessageResponse, MessageError> {
+        if let Some(body) = body {
+            body.validate()?;
+        }
+        let result = match (self.get_platform(), body) {
+            (Platform::Anthropic, _) | (_, None) => self.post("/messages", body, options).await,
+            (platform, Some(body)) => {
+                let path = platform.messages_path(&body.model, false);
+                let body =
+                    platform.messages_body(body, self.api_version(options), &self.betas(options));
+                self.post(&path, Some(&body), options).await
+            }
+        };
+        result.map_err(|e: MessageError| e.with_model(body.map_or("", |b| &b.model)))
+    }
+
+    async fn count_tokens_with_options<'a>(
+        &'a self,
+        body: Option<&'a CountMessageTokensParams>,
+        options: &'a RequestOptions,
+    ) -> Result<CountMessageTokensResponse, MessageError> {
+        if let Some(body) = body {
+            body.validate()?;
+        }
+        let cache = self
+            .get_count_tokens_cache()
+            .filter(|_| !options.alters_request());
+        let key = match (cache, body) {
+            (Some(cache), Some(body)) => {
+                let key = TokenCountCache::key(&body.to_wire_json());
+                let cached = cache.lock().unwrap_or_else(|e| e.into_inner()).get(key);
+                if let Some(input_tokens) = cached {
+                    return Ok(CountMessageTokensResponse { input_tokens });
+                }
+                Some(key)
+            }
+            _ => None,
+        };
+
+        let response: CountMessageTokensResponse = self
+            .post("/messages/count_tokens", body, options)
+            .await
+            .map_err(|e: MessageError| e.with_model(body.map_or("", |b| &b.model)))?;
+
+        if let (Some(cache), Some(key)) = (cache, key) {
+            cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(key, response.input_tokens);
+        }
+        Ok(response)
+    }
+
+    async fn create_message_streaming_with_options<'a>(
+        &'a self,
+        body: &'a CreateMessageParams,
+        options: &'a RequestOptions,
+    ) -> Result<MessageStream, MessageError> {
+        let response = self.send_streaming_request(body, options).await?;
+        Ok(MessageStream::new(parse_sse_stream(response)))
+    }
+}
+
+impl AnthropicClient {
+    /// Sends a streaming message request and returns the successful response
+    #[tracing::instrument(level = "debug", skip_all, fields(model = %body.model))]
+    async fn send_streaming_request(
+        &self,
+        body: &CreateMessageParams,
+        options: &RequestOptions,
+    ) -> Result<reqwest::Response, MessageError> {
         // Ensure that stream parameter is set to true
         if body.stream.is_none() || !body.stream.unwrap() {
             return Err(MessageError::ApiError(
                 "Stream parameter must be set to true for streaming".to_string(),
             ));
         }
+        body.validate()?;
 
-        let url = format!("{}/messages", AnthropicClient::DEFAULT_API_BASE_URL);
+        let platform = self.get_platform();
+        if let Platform::Bedrock { .. } = platform {
+            return Err(MessageError::ApiError(
+                "Streaming is not supported on Amazon Bedrock yet".to_string(),
+            ));
+        }
 
-        let client = &self.get_client();
-        let request = client
-            .request(reqwest::Method::POST, &url)
-            .header(
-                "x-api-key",
-                HeaderValue::from_str(self.get_api_key()).unwrap(),
-            )
-            .header(
-                "anthropic-version",
-                HeaderValue::from_str(self.get_api_version()).unwrap(),
-            )
-            .json(body);
+        let url = format!(
+            "{}{}",
+            self.get_api_base_url(),
+            platform.messages_path(&body.model, true)
+        );
 
-        let response = request
-            .send()
-            .await
-            .map_err(|e| MessageError::RequestFailed(e.to_string()))?;
+        let json = match platform {
+            Platform::Anthropic => serde_json::to_vec(body)?,
+            _ => serde_json::to_vec(&platform.messages_body(
+                body,
+                self.api_version(options),
+                &self.betas(options),
+            ))?,
+        };
+        let response = self
+            .send_to_url::<(), MessageError>(
+                reqwest::Method::POST,
+                &url,
+                None,
+                Some(&RequestBody::Json(json)),
+                options,
+            )
+            .await?;
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.map_err(|e| {
-                MessageError::RequestFailed(format!("Failed to read error response: {}", e))
-            })?;
-            return Err(MessageError::ApiError(error_text));
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = self.read_body::<MessageError>(response).await?;
+            let error_text = String::from_utf8_lossy(&error_body).into_owned();
+            return Err(
+                MessageError::from(ApiErrorResponse::new(status, error_text))
+                    .with_model(&body.model),
+            );
         }
 
-        // Get the bytes stream and convert it to EventSource stream
-        let bytes_stream = response.bytes_stream();
-        let event_stream = bytes_stream.eventsource();
+        Ok(response)
+    }
+}
+
+/// Parses the server-sent events of a streaming Messages API response
+///
+/// This is the parser used by `create_message_streaming`, exposed for callers
+/// that build and send the request themselves (for example to sign it) but
+/// still want the SDK to decode the event stream. The response status is not
+/// checked.
+///
+/// The body may be split at any byte. A multibyte UTF-8 character that spans
+/// two chunks is held back until it is complete, so text deltas are never
+/// corrupted by chunk boundaries.
+///
+/// `ping` events are skipped, and the stream ends at a `[DONE]` sentinel if
+/// the server (or a proxy in front of it) sends one. `error` events are
+/// returned as errors, typed after the error they carry: an
+/// `overloaded_error` becomes `MessageError::Overloaded`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anthropic_ai_sdk::messages::parse_sse_stream;
+/// use futures_util::StreamExt;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let response = reqwest::Client::new()
+///     .post("https://api.anthropic.com/v1/messages")
+///     .header("x-api-key", "your-api-key")
+///     .header("anthropic-version", "2023-06-01")
+///     .body(r#"{"model":"claude-3-5-sonnet-latest","max_tokens":1024,"stream":true,"messages":[{"role":"user","content":"Hello"}]}"#)
+///     .send()
+///     .await?;
+///
+/// let mut stream = parse_sse_stream(response);
+/// while let Some(event) = stream.next().await {
+///     println!("{:?}", event?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_sse_stream(
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<StreamEvent, MessageError>> {
+    // Get the bytes stream and convert it to EventSource stream. The
+    // eventsource decoder carries incomplete UTF-8 sequences over to the next
+    // chunk instead of decoding each chunk on its own.
+    let bytes_stream = response.bytes_stream();
+    let event_stream = bytes_stream.eventsource();
 
-        // Map SSE events to our StreamEvent type
-        Ok(event_stream.map(|event_result| {
-            event_result
+    // Map SSE events to our StreamEvent type
+    event_stream
+        .take_while(|event_result| {
+            let done = matches!(event_result, Ok(event) if event.data.trim() == "[DONE]");
+            future::ready(!done)
+        })
+        .filter_map(|event_result| {
+            let event = event_result
                 .map_err(|e| MessageError::RequestFailed(e.to_string()))
-                .and_then(|event| {
-                    serde_json::from_str::<StreamEvent>(&event.data).map_err(|e| {
-                        MessageError::ApiError(format!(
-                            "Failed to parse SSE event: {}. Event data: {}",
-                            e, event.data
-                        ))
-                    })
-                })
-        }))
+                .and_then(|event| parse_stream_event(&event.event, &event.data));
+            future::ready(match event {
+                Ok(StreamEvent::Ping) => None,
+                Ok(StreamEvent::Error { error }) => Some(Err(MessageError::from(
+                    ApiErrorResponse::from_detail(error),
+                ))),
+                event => Some(event),
+            })
+        })
+}
+
+/// Parses a single server-sent event into a `StreamEvent`
+///
+/// The `type` field of the data takes precedence. When the data is empty or
+/// has no `type`, the SSE event name is used instead.
+fn parse_stream_event(event_name: &str, data: &str) -> Result<StreamEvent, MessageError> {
+    let parse_error = |e: serde_json::Error| {
+        MessageError::ApiError(format!(
+            "Failed to parse SSE event: {}. Event data: {}",
+            e, data
+        ))
+    };
+
+    // "message" is the default name given to events without an `event:` field
+    let has_name = !event_name.is_empty() && event_name != "message";
+
+    if data.trim().is_empty() {
+        if !has_name {
+            return Err(MessageError::ApiError(
+                "Received an SSE event without data or event name".to_string(),
+            ));
+        }
+        return serde_json::from_value(serde_json::json!({ "type": event_name }))
+            .map_err(parse_error);
+    }
+
+    let mut value: serde_json::Value = serde_json::from_str(data).map_err(parse_error)?;
+    if let Some(object) = value.as_object_mut() {
+        if has_name && !object.contains_key("type") {
+            object.insert("type".to_string(), event_name.into());
+        }
     }
+    serde_json::from_value(value).map_err(parse_error)
 }
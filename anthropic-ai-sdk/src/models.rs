@@ -3,11 +3,15 @@
 //! This module contains the implementations for the Anthropic Models API endpoints.
 //! It provides functionality for listing available models and their capabilities.
 
-use crate::client::AnthropicClient;
+use crate::client::{AnthropicClient, RequestOptions};
+use crate::pagination::{Cursor, Page, paginate};
 use crate::types::model::{ListModelsParams, ListModelsResponse, Model, ModelClient, ModelError};
 use async_trait::async_trait;
+use futures_util::Stream;
 
-#[async_trait]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl ModelClient for AnthropicClient {
     /// Lists available models
     ///
@@ -57,7 +61,8 @@ impl ModelClient for AnthropicClient {
         &'a self,
         params: Option<&'a ListModelsParams>,
     ) -> Result<ListModelsResponse, ModelError> {
-        self.get("/models", params).await
+        self.list_models_with_options(params, &RequestOptions::default())
+            .await
     }
 
     /// Get a model
@@ -77,6 +82,7 @@ impl ModelClient for AnthropicClient {
     /// Returns a `ModelError` if:
     /// - The request fails to send
     /// - The API returns an error response
+    /// - The response is not a model object (`ModelError::UnexpectedResponse`)
     /// - The response cannot be parsed or is not a valid model
     ///
     /// # Examples
@@ -101,7 +107,97 @@ impl ModelClient for AnthropicClient {
     /// }
     /// ```
     async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<Model, ModelError> {
-        self.get(&format!("/models/{}", model_id), Option::<&()>::None)
+        self.get_model_with_options(model_id, &RequestOptions::default())
             .await
     }
+
+    /// Resolves a model alias
+    ///
+    /// Aliases such as `claude-3-5-sonnet-latest` move to newer snapshots over
+    /// time. The returned model carries the dated ID the alias points to
+    /// today, which can be pinned instead of the alias. A dated ID resolves to
+    /// itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ModelError` if:
+    /// - The request fails to send
+    /// - The API returns an error response, such as `ModelError::NotFound`
+    ///   for an unknown alias
+    /// - The response cannot be parsed or is not a valid model
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::model::{ModelClient, ModelError};
+    ///
+    /// # async fn example() -> Result<(), ModelError> {
+    /// let client = AnthropicClient::new::<ModelError>("your-api-key", "2023-06-01")?;
+    ///
+    /// let model = client.resolve_model_alias("claude-3-5-sonnet-latest").await?;
+    /// println!("claude-3-5-sonnet-latest is {}", model.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn resolve_model_alias<'a>(&'a self, alias: &'a str) -> Result<Model, ModelError> {
+        // The API resolves aliases itself when retrieving a model
+        self.get_model(alias).await
+    }
+
+    async fn list_models_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListModelsResponse, ModelError> {
+        self.get("/models", params, options).await
+    }
+
+    async fn get_model_with_options<'a>(
+        &'a self,
+        model_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Model, ModelError> {
+        let model: serde_json::Value = self
+            .get::<_, _, ModelError>(
+                &format!("/models/{}", model_id),
+                Option::<&()>::None,
+                options,
+            )
+            .await?;
+        // Checked first, so that another kind of object is not reported as
+        // an opaque parse error
+        match model.get("type").and_then(serde_json::Value::as_str) {
+            Some("model") => Ok(serde_json::from_value(model)?),
+            got => Err(ModelError::UnexpectedResponse {
+                expected: "model",
+                got: got.unwrap_or("none").to_string(),
+            }),
+        }
+    }
+
+    fn list_models_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> impl Stream<Item = Result<Model, ModelError>> + 'a {
+        let params = params.cloned().unwrap_or_default();
+        paginate(params.before_id.is_some(), move |cursor| {
+            let mut params = params.clone();
+            match cursor {
+                Some(Cursor::After(id)) => params.after_id = Some(id),
+                Some(Cursor::Before(id)) => params.before_id = Some(id),
+                Some(Cursor::Page(_)) | None => {}
+            }
+            async move {
+                let page = self.list_models(Some(&params)).await?;
+                Ok(Page {
+                    data: page.data,
+                    has_more: page.has_more,
+                    first_id: page.first_id,
+                    last_id: page.last_id,
+                    next_page: None,
+                })
+            }
+        })
+    }
 }
@@ -0,0 +1,69 @@
+//! Cursor pagination
+//!
+//! This module contains the helper shared by the `*_paginated` methods of the
+//! client traits to walk every page of a list endpoint.
+
+use futures_util::{Stream, TryStreamExt, stream};
+use std::future::Future;
+
+/// Cursor of the next page to fetch
+pub(crate) enum Cursor {
+    /// Fetch the page after this ID
+    After(String),
+    /// Fetch the page before this ID
+    Before(String),
+    /// Fetch the page identified by this opaque token
+    Page(String),
+}
+
+/// One page of a list endpoint
+pub(crate) struct Page<T> {
+    pub(crate) data: Vec<T>,
+    pub(crate) has_more: bool,
+    pub(crate) first_id: Option<String>,
+    pub(crate) last_id: Option<String>,
+    /// Token of the next page, for endpoints paginated with `page` instead of IDs
+    pub(crate) next_page: Option<String>,
+}
+
+/// Streams the items of every page of a list endpoint
+///
+/// `fetch_page` is called with `None` for the first page, which should use the
+/// caller's own parameters as is, and then with the cursor of each following
+/// page until a page reports `has_more: false`. A page that returns a
+/// `next_page` token is followed through it. Otherwise pages are walked
+/// backwards, following `first_id`, when `backward` is set (the caller passed
+/// a `before_id`), and forwards following `last_id`. Fetching stops after the
+/// first error.
+pub(crate) fn paginate<'a, T, E, F, Fut>(
+    backward: bool,
+    mut fetch_page: F,
+) -> impl Stream<Item = Result<T, E>> + 'a
+where
+    T: 'a,
+    E: 'a,
+    F: FnMut(Option<Cursor>) -> Fut + 'a,
+    Fut: Future<Output = Result<Page<T>, E>> + 'a,
+{
+    // The outer `None` marks that the last page has been fetched
+    let first: Option<Option<Cursor>> = Some(None);
+
+    stream::try_unfold(first, move |cursor| {
+        let page = cursor.map(&mut fetch_page);
+        async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let page = page.await?;
+            let next = match (page.has_more, page.next_page, backward) {
+                (false, _, _) => None,
+                (true, Some(next_page), _) => Some(Cursor::Page(next_page)),
+                (true, None, true) => page.first_id.map(Cursor::Before),
+                (true, None, false) => page.last_id.map(Cursor::After),
+            };
+            let items = stream::iter(page.data.into_iter().map(Ok));
+            Ok(Some((items, next.map(Some))))
+        }
+    })
+    .try_flatten()
+}
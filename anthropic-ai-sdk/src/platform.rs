@@ -0,0 +1,121 @@
+//! Cloud platforms serving Claude
+//!
+//! Amazon Bedrock and Google Vertex AI accept the same message bodies as the
+//! Anthropic API and return the same responses, but address the model in the
+//! URL, take the API version in the body and authenticate their own way. This
+//! module maps message requests to these conventions; authentication is left
+//! to `AnthropicClientBuilder::with_auth_header_fn`.
+
+use crate::types::message::CreateMessageParams;
+use serde_json::Value;
+
+/// Platform the client sends its requests to
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Platform {
+    /// The Anthropic API
+    #[default]
+    Anthropic,
+    /// Amazon Bedrock, in the given AWS region
+    Bedrock {
+        /// AWS region, such as `us-east-1`
+        region: String,
+    },
+    /// Google Vertex AI, in the given Google Cloud project and region
+    Vertex {
+        /// Google Cloud project ID
+        project: String,
+        /// Google Cloud region, such as `us-east5`, or `global`
+        region: String,
+    },
+}
+
+impl Platform {
+    /// API version sent to Amazon Bedrock
+    pub const BEDROCK_API_VERSION: &str = "bedrock-2023-05-31";
+
+    /// API version sent to Google Vertex AI
+    pub const VERTEX_API_VERSION: &str = "vertex-2023-10-16";
+
+    /// Returns the base URL of the platform's API
+    pub fn api_base_url(&self) -> String {
+        match self {
+            Platform::Anthropic => crate::client::AnthropicClient::DEFAULT_API_BASE_URL.to_string(),
+            Platform::Bedrock { region } => {
+                format!("https://bedrock-runtime.{region}.amazonaws.com")
+            }
+            Platform::Vertex { region, .. } if region == "global" => {
+                "https://aiplatform.googleapis.com/v1".to_string()
+            }
+            Platform::Vertex { region, .. } => {
+                format!("https://{region}-aiplatform.googleapis.com/v1")
+            }
+        }
+    }
+
+    /// Returns the path, relative to the base URL, creating a message with
+    /// the given model
+    pub(crate) fn messages_path(&self, model: &str, stream: bool) -> String {
+        match self {
+            Platform::Anthropic => "/messages".to_string(),
+            Platform::Bedrock { .. } => {
+                let action = if stream {
+                    "invoke-with-response-stream"
+                } else {
+                    "invoke"
+                };
+                format!("/model/{}/{action}", encode_path_segment(model))
+            }
+            Platform::Vertex { project, region } => {
+                let action = if stream {
+                    "streamRawPredict"
+                } else {
+                    "rawPredict"
+                };
+                format!(
+                    "/projects/{project}/locations/{region}/publishers/anthropic/models/{model}:{action}"
+                )
+            }
+        }
+    }
+
+    /// Returns the body creating a message
+    ///
+    /// The model moves to the path and the API version to the body. Bedrock
+    /// also takes the beta features in the body.
+    pub(crate) fn messages_body(
+        &self,
+        params: &CreateMessageParams,
+        api_version: &str,
+        betas: &[&str],
+    ) -> Value {
+        let mut body = params.to_wire_json();
+        if let (Platform::Bedrock { .. } | Platform::Vertex { .. }, Value::Object(fields)) =
+            (self, &mut body)
+        {
+            fields.remove("model");
+            fields.insert("anthropic_version".to_string(), api_version.into());
+            if matches!(self, Platform::Bedrock { .. }) {
+                // The stream is chosen by the path on Bedrock
+                fields.remove("stream");
+                if !betas.is_empty() {
+                    fields.insert("anthropic_beta".to_string(), betas.into());
+                }
+            }
+        }
+        body
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, as the AWS SDKs do
+/// for model IDs and ARNs
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,38 @@
+//! Commonly used types
+//!
+//! Glob-importing this module brings in the client, the client traits whose
+//! methods it implements, and the types most requests and responses are
+//! made of.
+//!
+//! ```no_run
+//! use anthropic_ai_sdk::prelude::*;
+//!
+//! # async fn example() -> Result<(), MessageError> {
+//! let client = AnthropicClient::new::<MessageError>("your-api-key", "2023-06-01")?;
+//! let params = CreateMessageParams::new(RequiredMessageParams {
+//!     model: "claude-3-5-sonnet-latest".to_string(),
+//!     messages: vec![Message::new_text(Role::User, "Hello, Claude")],
+//!     max_tokens: 1024,
+//! });
+//! let response = client.create_message(Some(&params)).await?;
+//! println!("{}", response.text());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The Message Batches API has its own `Message` type, which is not part of
+//! the prelude; import it from `types::message_batches`.
+
+pub use crate::client::{AnthropicClient, AnthropicClientBuilder, RequestOptions};
+pub use crate::types::admin::api_keys::{AdminClient, AdminError};
+pub use crate::types::files::{FileError, FilesClient};
+pub use crate::types::message::{
+    ContentBlock, CreateMessageParams, CreateMessageResponse, Message, MessageAccumulator,
+    MessageClient, MessageError, MessageStream, RequiredMessageParams, Role, StopReason,
+    StreamEvent, SystemPrompt, Tool, ToolChoice, Usage,
+};
+pub use crate::types::message_batches::{
+    CreateMessageBatchParams, MessageBatch, MessageBatchClient, MessageBatchError, MessageRequest,
+    MessageRequestParams, PollConfig, ProcessingStatus,
+};
+pub use crate::types::model::{ModelClient, ModelError};
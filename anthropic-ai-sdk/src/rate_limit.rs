@@ -0,0 +1,59 @@
+//! Client-side rate limiting
+//!
+//! This module contains the token bucket consulted before every outbound
+//! request when it is enabled with `AnthropicClientBuilder::with_rate_limit`.
+
+use crate::timer::{self, Instant};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Token bucket of requests
+///
+/// The bucket holds up to `capacity` permits and is refilled continuously at
+/// `capacity` permits per minute. A request that finds the bucket empty
+/// reserves the next permit anyway, driving the count below zero, and sleeps
+/// until that permit is due. Concurrent callers thus queue up in order instead
+/// of all waking up at once.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    capacity: f64,
+    per_second: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    permits: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_minute: u32) -> Self {
+        let capacity = f64::from(requests_per_minute.max(1));
+        Self {
+            capacity,
+            per_second: capacity / 60.0,
+            state: Mutex::new(BucketState {
+                permits: capacity,
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits until a request may be sent
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.updated_at).as_secs_f64();
+            state.permits = (state.permits + elapsed * self.per_second).min(self.capacity);
+            state.updated_at = now;
+            state.permits -= 1.0;
+            if state.permits >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-state.permits / self.per_second)
+        };
+        timer::sleep(wait).await;
+    }
+}
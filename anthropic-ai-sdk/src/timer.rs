@@ -0,0 +1,27 @@
+//! Clocks and sleeps used by the client
+//!
+//! Tokio's timers and `std::time` clocks panic on `wasm32-unknown-unknown`,
+//! so the browser build uses `wasmtimer`, which is backed by the JavaScript
+//! event loop and clock. Everything else uses Tokio.
+
+use std::time::SystemTime;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::{Instant, sleep};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasmtimer::{std::Instant, tokio::sleep};
+
+/// Returns the current wall clock time
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// Returns the current wall clock time
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn now() -> SystemTime {
+    let since_epoch = wasmtimer::std::SystemTime::now()
+        .duration_since(wasmtimer::std::UNIX_EPOCH)
+        .unwrap_or_default();
+    SystemTime::UNIX_EPOCH + since_epoch
+}
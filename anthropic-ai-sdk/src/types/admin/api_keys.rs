@@ -2,7 +2,11 @@
 //!
 //! This module contains the types and functions for the Anthropic Admin API.
 //!
+use crate::client::RequestOptions;
+use crate::types::error::{ApiErrorResponse, ResponseTooLarge};
 use async_trait::async_trait;
+use futures_util::Stream;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use time::OffsetDateTime;
@@ -19,6 +23,24 @@ pub enum AdminError {
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+    #[error("API error ({status}): {message}")]
+    ApiStatus { status: u16, message: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLarge),
 }
 
 impl From<String> for AdminError {
@@ -27,7 +49,57 @@ impl From<String> for AdminError {
     }
 }
 
-#[async_trait]
+impl From<ApiErrorResponse> for AdminError {
+    fn from(error: ApiErrorResponse) -> Self {
+        match error.status.as_u16() {
+            401 => AdminError::Unauthorized(error.message().to_string()),
+            403 => AdminError::PermissionDenied(error.message().to_string()),
+            404 => AdminError::NotFound(error.message().to_string()),
+            429 => AdminError::RateLimited(error.message().to_string()),
+            529 => AdminError::Overloaded(error.message().to_string()),
+            status => AdminError::ApiStatus {
+                status,
+                message: error.message().to_string(),
+            },
+        }
+    }
+}
+
+impl AdminError {
+    /// Returns the HTTP status code of the failed response, if there was one
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            AdminError::Unauthorized(_) => Some(401),
+            AdminError::PermissionDenied(_) => Some(403),
+            AdminError::NotFound(_) => Some(404),
+            AdminError::RateLimited(_) => Some(429),
+            AdminError::Overloaded(_) => Some(529),
+            AdminError::ApiStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the failed request is worth retrying
+    ///
+    /// Rate limits, overloaded and other server errors, and requests that
+    /// failed to send are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AdminError::RequestFailed(_)
+            | AdminError::RateLimited(_)
+            | AdminError::Overloaded(_) => true,
+            AdminError::ApiStatus { status, .. } => {
+                StatusCode::from_u16(*status).is_ok_and(ApiErrorResponse::is_retryable_status)
+            }
+            AdminError::Http(error) => ApiErrorResponse::is_retryable_error(error),
+            _ => false,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait AdminClient {
     async fn list_api_keys<'a>(
         &'a self,
@@ -36,15 +108,69 @@ pub trait AdminClient {
 
     async fn get_api_key<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKey, AdminError>;
 
+    /// Stream all API keys, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_api_keys_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+    ) -> impl Stream<Item = Result<ApiKey, AdminError>> + 'a
+    where
+        Self: Sized;
+
     async fn update_api_key<'a>(
         &'a self,
         api_key_id: &'a str,
         params: &'a AdminUpdateApiKeyParams,
     ) -> Result<ApiKey, AdminError>;
+
+    /// Archive an API key, permanently disabling it
+    ///
+    /// The Admin API has no endpoints to create or delete API keys: keys are
+    /// created in the Console and retired by archiving them.
+    async fn archive_api_key<'a>(&'a self, api_key_id: &'a str) -> Result<ApiKey, AdminError>;
+
+    /// Same as `list_api_keys`, with per-request options
+    ///
+    /// Defaults to `list_api_keys`, ignoring `options`.
+    async fn list_api_keys_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListApiKeysParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListApiKeysResponse, AdminError> {
+        let _ = options;
+        self.list_api_keys(params).await
+    }
+
+    /// Same as `get_api_key`, with per-request options
+    ///
+    /// Defaults to `get_api_key`, ignoring `options`.
+    async fn get_api_key_with_options<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<ApiKey, AdminError> {
+        let _ = options;
+        self.get_api_key(api_key_id).await
+    }
+
+    /// Same as `update_api_key`, with per-request options
+    ///
+    /// Defaults to `update_api_key`, ignoring `options`.
+    async fn update_api_key_with_options<'a>(
+        &'a self,
+        api_key_id: &'a str,
+        params: &'a AdminUpdateApiKeyParams,
+        options: &'a RequestOptions,
+    ) -> Result<ApiKey, AdminError> {
+        let _ = options;
+        self.update_api_key(api_key_id, params).await
+    }
 }
 
 /// Parameters for listing API keys
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct ListApiKeysParams {
     /// Cursor for pagination (before)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -110,12 +236,31 @@ impl ListApiKeysParams {
 }
 
 /// API key status
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Statuses added to the API after this version of the crate deserialize as
+/// `Unknown` instead of failing the whole listing.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::api_keys::ApiKeyStatus;
+///
+/// let status: ApiKeyStatus = serde_json::from_str("\"archived\"").unwrap();
+/// assert_eq!(status, ApiKeyStatus::Archived);
+/// assert_eq!(serde_json::to_string(&status).unwrap(), "\"archived\"");
+///
+/// let status: ApiKeyStatus = serde_json::from_str("\"pending\"").unwrap();
+/// assert_eq!(status, ApiKeyStatus::Unknown);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ApiKeyStatus {
     Active,
     Inactive,
     Archived,
+    /// A status not known to this version of the crate
+    #[serde(other)]
+    Unknown,
 }
 
 /// Response structure for listing API keys
@@ -166,7 +311,7 @@ pub struct ApiKey {
 }
 
 /// Parameters for updating an API key
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct AdminUpdateApiKeyParams {
     /// Name of the API key
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -177,12 +322,9 @@ pub struct AdminUpdateApiKeyParams {
 }
 
 impl AdminUpdateApiKeyParams {
-    /// Create a new UpdateApiKeyParams with default values
+    /// Create a new AdminUpdateApiKeyParams with default values
     pub fn new() -> Self {
-        Self {
-            name: None,
-            status: None,
-        }
+        Self::default()
     }
 
     /// Set the name of the API key
@@ -197,3 +339,6 @@ impl AdminUpdateApiKeyParams {
         self
     }
 }
+
+/// Alias of [`AdminUpdateApiKeyParams`] under the name used by older docs
+pub type UpdateApiKeyParams = AdminUpdateApiKeyParams;
@@ -0,0 +1,211 @@
+//! Admin Organization Invites API
+//!
+//! This module contains the types and functions for inviting users to an organization.
+//!
+use crate::client::RequestOptions;
+use crate::types::admin::api_keys::AdminError;
+use crate::types::admin::users::OrganizationRole;
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait InviteClient {
+    async fn list_invites<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> Result<ListInvitesResponse, AdminError>;
+
+    /// Stream all invites, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_invites_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+    ) -> impl Stream<Item = Result<Invite, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    async fn get_invite<'a>(&'a self, invite_id: &'a str) -> Result<Invite, AdminError>;
+
+    async fn create_invite<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+    ) -> Result<Invite, AdminError>;
+
+    async fn delete_invite<'a>(&'a self, invite_id: &'a str) -> Result<InviteDeleted, AdminError>;
+
+    /// Same as `list_invites`, with per-request options
+    ///
+    /// Defaults to `list_invites`, ignoring `options`.
+    async fn list_invites_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListInvitesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListInvitesResponse, AdminError> {
+        let _ = options;
+        self.list_invites(params).await
+    }
+
+    /// Same as `get_invite`, with per-request options
+    ///
+    /// Defaults to `get_invite`, ignoring `options`.
+    async fn get_invite_with_options<'a>(
+        &'a self,
+        invite_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Invite, AdminError> {
+        let _ = options;
+        self.get_invite(invite_id).await
+    }
+
+    /// Same as `create_invite`, with per-request options
+    ///
+    /// Defaults to `create_invite`, ignoring `options`.
+    async fn create_invite_with_options<'a>(
+        &'a self,
+        email: &'a str,
+        role: OrganizationRole,
+        options: &'a RequestOptions,
+    ) -> Result<Invite, AdminError> {
+        let _ = options;
+        self.create_invite(email, role).await
+    }
+
+    /// Same as `delete_invite`, with per-request options
+    ///
+    /// Defaults to `delete_invite`, ignoring `options`.
+    async fn delete_invite_with_options<'a>(
+        &'a self,
+        invite_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<InviteDeleted, AdminError> {
+        let _ = options;
+        self.delete_invite(invite_id).await
+    }
+}
+
+/// Status of an invite
+///
+/// Statuses added to the API after this version of the crate deserialize as
+/// `Unknown` instead of failing the whole listing.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::invites::InviteStatus;
+///
+/// let status: InviteStatus = serde_json::from_str("\"pending\"").unwrap();
+/// assert_eq!(status, InviteStatus::Pending);
+///
+/// let status: InviteStatus = serde_json::from_str("\"revoked\"").unwrap();
+/// assert_eq!(status, InviteStatus::Unknown);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InviteStatus {
+    Accepted,
+    Expired,
+    Deleted,
+    Pending,
+    /// A status not known to this version of the crate
+    #[serde(other)]
+    Unknown,
+}
+
+/// Parameters for listing invites
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct ListInvitesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListInvitesParams {
+    /// Create a new ListInvitesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter (1-1000)
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.clamp(1, 1000));
+        self
+    }
+}
+
+/// Response structure for listing invites
+#[derive(Debug, Deserialize)]
+pub struct ListInvitesResponse {
+    /// List of invites
+    pub data: Vec<Invite>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// Represents an invite to join the organization
+#[derive(Debug, Deserialize)]
+pub struct Invite {
+    /// Unique identifier for the invite
+    pub id: String,
+    /// Type of the resource (always "invite")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Email of the invited user
+    pub email: String,
+    /// Role the user will have in the organization
+    pub role: OrganizationRole,
+    /// Status of the invite
+    pub status: InviteStatus,
+    /// When the invite was sent
+    #[serde(with = "rfc3339")]
+    pub invited_at: OffsetDateTime,
+    /// When the invite expires
+    #[serde(with = "rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+/// Response of deleting an invite
+#[derive(Debug, Deserialize)]
+pub struct InviteDeleted {
+    /// ID of the deleted invite
+    pub id: String,
+    /// Type of the resource (always "invite_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Request body for creating an invite
+#[derive(Debug, Serialize)]
+pub(crate) struct CreateInviteBody<'a> {
+    pub(crate) email: &'a str,
+    pub(crate) role: OrganizationRole,
+}
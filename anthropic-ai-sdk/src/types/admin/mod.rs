@@ -1 +1,6 @@
 pub mod api_keys;
+pub mod invites;
+pub mod reporting;
+pub mod users;
+pub mod workspace_members;
+pub mod workspaces;
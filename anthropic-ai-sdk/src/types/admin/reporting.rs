@@ -0,0 +1,427 @@
+//! Admin Reporting API
+//!
+//! This module contains the types and functions for the usage and cost reports of the Anthropic Admin API.
+//!
+use crate::client::RequestOptions;
+use crate::types::admin::api_keys::AdminError;
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use time::serde::rfc3339;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait UsageReportClient {
+    /// Get one page of the token usage report for the Messages API
+    async fn get_usage_report<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> Result<UsageReport, AdminError>;
+
+    /// Stream the buckets of every page of the usage report
+    fn get_usage_report_paginated<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+    ) -> impl Stream<Item = Result<UsageBucket, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    /// Same as `get_usage_report`, with per-request options
+    ///
+    /// Defaults to `get_usage_report`, ignoring `options`.
+    async fn get_usage_report_with_options<'a>(
+        &'a self,
+        params: &'a UsageReportParams,
+        options: &'a RequestOptions,
+    ) -> Result<UsageReport, AdminError> {
+        let _ = options;
+        self.get_usage_report(params).await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait CostReportClient {
+    /// Get one page of the cost report
+    async fn get_cost_report<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> Result<CostReport, AdminError>;
+
+    /// Stream the buckets of every page of the cost report
+    fn get_cost_report_paginated<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+    ) -> impl Stream<Item = Result<CostBucket, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    /// Same as `get_cost_report`, with per-request options
+    ///
+    /// Defaults to `get_cost_report`, ignoring `options`.
+    async fn get_cost_report_with_options<'a>(
+        &'a self,
+        params: &'a CostReportParams,
+        options: &'a RequestOptions,
+    ) -> Result<CostReport, AdminError> {
+        let _ = options;
+        self.get_cost_report(params).await
+    }
+}
+
+/// Time granularity of report buckets
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BucketWidth {
+    /// One minute buckets
+    #[serde(rename = "1m")]
+    Minute,
+    /// One hour buckets
+    #[serde(rename = "1h")]
+    Hour,
+    /// One day buckets
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl BucketWidth {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BucketWidth::Minute => "1m",
+            BucketWidth::Hour => "1h",
+            BucketWidth::Day => "1d",
+        }
+    }
+}
+
+/// Dimension to break the usage report down by
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGroupBy {
+    /// Group by API key
+    ApiKeyId,
+    /// Group by workspace
+    WorkspaceId,
+    /// Group by model
+    Model,
+}
+
+impl UsageGroupBy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UsageGroupBy::ApiKeyId => "api_key_id",
+            UsageGroupBy::WorkspaceId => "workspace_id",
+            UsageGroupBy::Model => "model",
+        }
+    }
+}
+
+/// Dimension to break the cost report down by
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CostGroupBy {
+    /// Group by workspace
+    WorkspaceId,
+    /// Group by description of the cost, such as the model and token type
+    Description,
+}
+
+impl CostGroupBy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CostGroupBy::WorkspaceId => "workspace_id",
+            CostGroupBy::Description => "description",
+        }
+    }
+}
+
+/// Formats a report timestamp as the RFC 3339 string expected by the API
+fn format_time(time: &OffsetDateTime) -> String {
+    time.format(&Rfc3339)
+        .expect("an OffsetDateTime is always representable in RFC 3339")
+}
+
+/// Parameters for the Messages API usage report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageReportParams {
+    /// Start of the report, inclusive, aligned to the bucket width
+    pub starting_at: OffsetDateTime,
+    /// End of the report, exclusive
+    pub ending_at: Option<OffsetDateTime>,
+    /// Width of each bucket (defaults to one day)
+    pub bucket_width: Option<BucketWidth>,
+    /// Dimensions to break each bucket down by
+    pub group_by: Vec<UsageGroupBy>,
+    /// Maximum number of buckets per page
+    pub limit: Option<u16>,
+    /// Token of the page to fetch, from the previous response's `next_page`
+    pub page: Option<String>,
+}
+
+impl UsageReportParams {
+    /// Create new UsageReportParams starting at the given time
+    pub fn new(starting_at: OffsetDateTime) -> Self {
+        Self {
+            starting_at,
+            ending_at: None,
+            bucket_width: None,
+            group_by: Vec::new(),
+            limit: None,
+            page: None,
+        }
+    }
+
+    /// Set the end of the report
+    pub fn ending_at(mut self, ending_at: OffsetDateTime) -> Self {
+        self.ending_at = Some(ending_at);
+        self
+    }
+
+    /// Set the width of each bucket
+    pub fn bucket_width(mut self, bucket_width: BucketWidth) -> Self {
+        self.bucket_width = Some(bucket_width);
+        self
+    }
+
+    /// Add a dimension to break each bucket down by
+    pub fn group_by(mut self, group_by: UsageGroupBy) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Set the maximum number of buckets per page
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the page token
+    pub fn page(mut self, page: impl Into<String>) -> Self {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// Builds the query string pairs, repeating `group_by[]` for each dimension
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", format_time(&self.starting_at))];
+        if let Some(ending_at) = &self.ending_at {
+            query.push(("ending_at", format_time(ending_at)));
+        }
+        if let Some(bucket_width) = &self.bucket_width {
+            query.push(("bucket_width", bucket_width.as_str().to_string()));
+        }
+        for group_by in &self.group_by {
+            query.push(("group_by[]", group_by.as_str().to_string()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+/// Parameters for the cost report
+///
+/// Costs are always reported in one day buckets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostReportParams {
+    /// Start of the report, inclusive, aligned to the day
+    pub starting_at: OffsetDateTime,
+    /// End of the report, exclusive
+    pub ending_at: Option<OffsetDateTime>,
+    /// Dimensions to break each bucket down by
+    pub group_by: Vec<CostGroupBy>,
+    /// Maximum number of buckets per page
+    pub limit: Option<u16>,
+    /// Token of the page to fetch, from the previous response's `next_page`
+    pub page: Option<String>,
+}
+
+impl CostReportParams {
+    /// Create new CostReportParams starting at the given time
+    pub fn new(starting_at: OffsetDateTime) -> Self {
+        Self {
+            starting_at,
+            ending_at: None,
+            group_by: Vec::new(),
+            limit: None,
+            page: None,
+        }
+    }
+
+    /// Set the end of the report
+    pub fn ending_at(mut self, ending_at: OffsetDateTime) -> Self {
+        self.ending_at = Some(ending_at);
+        self
+    }
+
+    /// Add a dimension to break each bucket down by
+    pub fn group_by(mut self, group_by: CostGroupBy) -> Self {
+        self.group_by.push(group_by);
+        self
+    }
+
+    /// Set the maximum number of buckets per page
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the page token
+    pub fn page(mut self, page: impl Into<String>) -> Self {
+        self.page = Some(page.into());
+        self
+    }
+
+    /// Builds the query string pairs, repeating `group_by[]` for each dimension
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("starting_at", format_time(&self.starting_at))];
+        if let Some(ending_at) = &self.ending_at {
+            query.push(("ending_at", format_time(ending_at)));
+        }
+        for group_by in &self.group_by {
+            query.push(("group_by[]", group_by.as_str().to_string()));
+        }
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(page) = &self.page {
+            query.push(("page", page.clone()));
+        }
+        query
+    }
+}
+
+/// One page of the usage report
+#[derive(Debug, Deserialize)]
+pub struct UsageReport {
+    /// Usage buckets, in chronological order
+    pub data: Vec<UsageBucket>,
+    /// Indicates if there are more buckets
+    pub has_more: bool,
+    /// Token of the next page, if there is one
+    pub next_page: Option<String>,
+}
+
+/// Usage over one time bucket
+#[derive(Debug, Deserialize)]
+pub struct UsageBucket {
+    /// Start of the bucket, inclusive
+    #[serde(with = "rfc3339")]
+    pub starting_at: OffsetDateTime,
+    /// End of the bucket, exclusive
+    #[serde(with = "rfc3339")]
+    pub ending_at: OffsetDateTime,
+    /// Usage in the bucket
+    pub results: Vec<UsageResult>,
+}
+
+/// Token usage aggregated over a bucket
+///
+/// The grouping keys are only set for the dimensions the report was grouped by.
+#[derive(Debug, Deserialize)]
+pub struct UsageResult {
+    /// API key the usage belongs to
+    pub api_key_id: Option<String>,
+    /// Workspace the usage belongs to
+    pub workspace_id: Option<String>,
+    /// Model the usage belongs to
+    pub model: Option<String>,
+    /// Input tokens that were neither read from nor written to the cache
+    #[serde(default)]
+    pub uncached_input_tokens: u64,
+    /// Input tokens read from the prompt cache
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_creation: CacheCreationUsage,
+    /// Output tokens generated
+    #[serde(default)]
+    pub output_tokens: u64,
+}
+
+impl UsageResult {
+    /// Returns all input tokens, whether uncached, read from or written to the cache
+    pub fn input_tokens(&self) -> u64 {
+        self.uncached_input_tokens
+            + self.cache_read_input_tokens
+            + self.cache_creation.ephemeral_5m_input_tokens
+            + self.cache_creation.ephemeral_1h_input_tokens
+    }
+}
+
+/// Input tokens written to the prompt cache, by cache lifetime
+#[derive(Debug, Deserialize, Default)]
+pub struct CacheCreationUsage {
+    /// Tokens written to a cache entry living five minutes
+    #[serde(default)]
+    pub ephemeral_5m_input_tokens: u64,
+    /// Tokens written to a cache entry living one hour
+    #[serde(default)]
+    pub ephemeral_1h_input_tokens: u64,
+}
+
+/// One page of the cost report
+#[derive(Debug, Deserialize)]
+pub struct CostReport {
+    /// Cost buckets, in chronological order
+    pub data: Vec<CostBucket>,
+    /// Indicates if there are more buckets
+    pub has_more: bool,
+    /// Token of the next page, if there is one
+    pub next_page: Option<String>,
+}
+
+/// Costs over one day
+#[derive(Debug, Deserialize)]
+pub struct CostBucket {
+    /// Start of the bucket, inclusive
+    #[serde(with = "rfc3339")]
+    pub starting_at: OffsetDateTime,
+    /// End of the bucket, exclusive
+    #[serde(with = "rfc3339")]
+    pub ending_at: OffsetDateTime,
+    /// Costs in the bucket
+    pub results: Vec<CostResult>,
+}
+
+/// Cost aggregated over a bucket
+///
+/// The grouping keys are only set for the dimensions the report was grouped by.
+#[derive(Debug, Deserialize)]
+pub struct CostResult {
+    /// Currency of the amount, such as `USD`
+    pub currency: String,
+    /// Cost as a decimal string in the lowest unit of the currency (cents for USD)
+    pub amount: String,
+    /// Workspace the cost belongs to
+    pub workspace_id: Option<String>,
+    /// Description of the cost
+    pub description: Option<String>,
+    /// Kind of cost, such as `tokens` or `web_search`, when grouped by description
+    pub cost_type: Option<String>,
+    /// Model the cost belongs to, when grouped by description
+    pub model: Option<String>,
+    /// Kind of tokens billed, such as `uncached_input_tokens`, when grouped by description
+    pub token_type: Option<String>,
+    /// Service tier the cost belongs to, when grouped by description
+    pub service_tier: Option<String>,
+    /// Context window the cost belongs to, when grouped by description
+    pub context_window: Option<String>,
+}
+
+impl CostResult {
+    /// Parses the amount, in the lowest unit of the currency
+    ///
+    /// Returns `None` if the API sent an amount that is not a decimal number.
+    pub fn amount_as_f64(&self) -> Option<f64> {
+        self.amount.parse().ok()
+    }
+}
@@ -0,0 +1,222 @@
+//! Admin Organization Members API
+//!
+//! This module contains the types and functions for managing the users of an organization.
+//!
+use crate::client::RequestOptions;
+use crate::types::admin::api_keys::AdminError;
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait UserClient {
+    async fn list_users<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+    ) -> Result<ListUsersResponse, AdminError>;
+
+    /// Stream all users of the organization, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_users_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+    ) -> impl Stream<Item = Result<OrganizationUser, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    async fn get_user<'a>(&'a self, user_id: &'a str) -> Result<OrganizationUser, AdminError>;
+
+    async fn update_user_role<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+    ) -> Result<OrganizationUser, AdminError>;
+
+    async fn remove_user<'a>(&'a self, user_id: &'a str) -> Result<UserDeleted, AdminError>;
+
+    /// Same as `list_users`, with per-request options
+    ///
+    /// Defaults to `list_users`, ignoring `options`.
+    async fn list_users_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListUsersParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListUsersResponse, AdminError> {
+        let _ = options;
+        self.list_users(params).await
+    }
+
+    /// Same as `get_user`, with per-request options
+    ///
+    /// Defaults to `get_user`, ignoring `options`.
+    async fn get_user_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<OrganizationUser, AdminError> {
+        let _ = options;
+        self.get_user(user_id).await
+    }
+
+    /// Same as `update_user_role`, with per-request options
+    ///
+    /// Defaults to `update_user_role`, ignoring `options`.
+    async fn update_user_role_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        role: OrganizationRole,
+        options: &'a RequestOptions,
+    ) -> Result<OrganizationUser, AdminError> {
+        let _ = options;
+        self.update_user_role(user_id, role).await
+    }
+
+    /// Same as `remove_user`, with per-request options
+    ///
+    /// Defaults to `remove_user`, ignoring `options`.
+    async fn remove_user_with_options<'a>(
+        &'a self,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<UserDeleted, AdminError> {
+        let _ = options;
+        self.remove_user(user_id).await
+    }
+}
+
+/// Role of a user in the organization
+///
+/// Roles added to the API after this version of the crate deserialize as
+/// `Other` instead of failing the whole listing. `Other` cannot be sent back,
+/// so serializing it is an error.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::users::OrganizationRole;
+///
+/// let role: OrganizationRole = serde_json::from_str("\"claude_code_user\"").unwrap();
+/// assert_eq!(role, OrganizationRole::ClaudeCodeUser);
+/// assert_eq!(serde_json::to_string(&role).unwrap(), "\"claude_code_user\"");
+///
+/// let role: OrganizationRole = serde_json::from_str("\"auditor\"").unwrap();
+/// assert_eq!(role, OrganizationRole::Other);
+/// assert!(serde_json::to_string(&role).is_err());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRole {
+    User,
+    Developer,
+    Billing,
+    Admin,
+    ClaudeCodeUser,
+    /// A role not known to this version of the crate
+    #[serde(other, skip_serializing)]
+    Other,
+}
+
+/// Parameters for listing users
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct ListUsersParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    /// Filter by user email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl ListUsersParams {
+    /// Create a new ListUsersParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter (1-1000)
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.clamp(1, 1000));
+        self
+    }
+
+    /// Set the email parameter
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+}
+
+/// Response structure for listing users
+#[derive(Debug, Deserialize)]
+pub struct ListUsersResponse {
+    /// List of users
+    pub data: Vec<OrganizationUser>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// Represents a member of the organization
+///
+/// The [`User`](crate::types::admin::api_keys::User) referenced by other
+/// resources only carries the ID; this is the full user.
+#[derive(Debug, Deserialize)]
+pub struct OrganizationUser {
+    /// Unique identifier for the user
+    pub id: String,
+    /// Type of the resource (always "user")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Email of the user
+    pub email: String,
+    /// Name of the user
+    pub name: String,
+    /// Role of the user in the organization
+    pub role: OrganizationRole,
+    /// When the user joined the organization
+    #[serde(with = "rfc3339")]
+    pub added_at: OffsetDateTime,
+}
+
+/// Response of removing a user from the organization
+#[derive(Debug, Deserialize)]
+pub struct UserDeleted {
+    /// ID of the removed user
+    pub id: String,
+    /// Type of the resource (always "user_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// Request body for updating a user
+#[derive(Debug, Serialize)]
+pub(crate) struct UpdateUserBody {
+    pub(crate) role: OrganizationRole,
+}
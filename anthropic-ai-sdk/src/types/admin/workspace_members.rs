@@ -0,0 +1,251 @@
+//! Admin Workspace Members API
+//!
+//! This module contains the types and functions for managing the members of a workspace.
+//!
+use crate::client::RequestOptions;
+use crate::types::admin::api_keys::AdminError;
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait WorkspaceMemberClient {
+    async fn list_workspace_members<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError>;
+
+    /// Stream all members of a workspace, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_workspace_members_paginated<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+    ) -> impl Stream<Item = Result<WorkspaceMember, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    async fn get_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    async fn add_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    async fn update_workspace_member_role<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+    ) -> Result<WorkspaceMember, AdminError>;
+
+    async fn delete_workspace_member<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+    ) -> Result<WorkspaceMemberDeleted, AdminError>;
+
+    /// Same as `list_workspace_members`, with per-request options
+    ///
+    /// Defaults to `list_workspace_members`, ignoring `options`.
+    async fn list_workspace_members_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: Option<&'a ListWorkspaceMembersParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListWorkspaceMembersResponse, AdminError> {
+        let _ = options;
+        self.list_workspace_members(workspace_id, params).await
+    }
+
+    /// Same as `get_workspace_member`, with per-request options
+    ///
+    /// Defaults to `get_workspace_member`, ignoring `options`.
+    async fn get_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        let _ = options;
+        self.get_workspace_member(workspace_id, user_id).await
+    }
+
+    /// Same as `add_workspace_member`, with per-request options
+    ///
+    /// Defaults to `add_workspace_member`, ignoring `options`.
+    async fn add_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        let _ = options;
+        self.add_workspace_member(workspace_id, user_id, role).await
+    }
+
+    /// Same as `update_workspace_member_role`, with per-request options
+    ///
+    /// Defaults to `update_workspace_member_role`, ignoring `options`.
+    async fn update_workspace_member_role_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        role: WorkspaceRole,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMember, AdminError> {
+        let _ = options;
+        self.update_workspace_member_role(workspace_id, user_id, role)
+            .await
+    }
+
+    /// Same as `delete_workspace_member`, with per-request options
+    ///
+    /// Defaults to `delete_workspace_member`, ignoring `options`.
+    async fn delete_workspace_member_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        user_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<WorkspaceMemberDeleted, AdminError> {
+        let _ = options;
+        self.delete_workspace_member(workspace_id, user_id).await
+    }
+}
+
+/// Role of a user in a workspace
+///
+/// Roles added to the API after this version of the crate deserialize as
+/// `Other` instead of failing the whole listing. `Other` cannot be sent back,
+/// so serializing it is an error.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceRole;
+///
+/// let role: WorkspaceRole = serde_json::from_str("\"workspace_admin\"").unwrap();
+/// assert_eq!(role, WorkspaceRole::Admin);
+/// assert_eq!(serde_json::to_string(&role).unwrap(), "\"workspace_admin\"");
+///
+/// let role: WorkspaceRole = serde_json::from_str("\"workspace_auditor\"").unwrap();
+/// assert_eq!(role, WorkspaceRole::Other);
+/// assert!(serde_json::to_string(&role).is_err());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceRole {
+    #[serde(rename = "workspace_user")]
+    User,
+    #[serde(rename = "workspace_developer")]
+    Developer,
+    #[serde(rename = "workspace_admin")]
+    Admin,
+    #[serde(rename = "workspace_billing")]
+    Billing,
+    /// A role not known to this version of the crate
+    #[serde(other, skip_serializing)]
+    Other,
+}
+
+/// Parameters for listing workspace members
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct ListWorkspaceMembersParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListWorkspaceMembersParams {
+    /// Create a new ListWorkspaceMembersParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter (1-1000)
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.clamp(1, 1000));
+        self
+    }
+}
+
+/// Response structure for listing workspace members
+#[derive(Debug, Deserialize)]
+pub struct ListWorkspaceMembersResponse {
+    /// List of workspace members
+    pub data: Vec<WorkspaceMember>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// Represents the membership of a user in a workspace
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceMember {
+    /// Type of the resource (always "workspace_member")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// ID of the user
+    pub user_id: String,
+    /// ID of the workspace
+    pub workspace_id: String,
+    /// Role of the user in the workspace
+    pub workspace_role: WorkspaceRole,
+}
+
+/// Response of deleting a workspace member
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceMemberDeleted {
+    /// Type of the resource (always "workspace_member_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// ID of the removed user
+    pub user_id: String,
+    /// ID of the workspace
+    pub workspace_id: String,
+}
+
+/// Request body for adding a workspace member
+#[derive(Debug, Serialize)]
+pub(crate) struct AddWorkspaceMemberBody<'a> {
+    pub(crate) user_id: &'a str,
+    pub(crate) workspace_role: WorkspaceRole,
+}
+
+/// Request body for updating a workspace member
+#[derive(Debug, Serialize)]
+pub(crate) struct UpdateWorkspaceMemberBody {
+    pub(crate) workspace_role: WorkspaceRole,
+}
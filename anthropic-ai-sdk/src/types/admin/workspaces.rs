@@ -0,0 +1,220 @@
+//! Admin Workspaces API
+//!
+//! This module contains the types and functions for managing the workspaces of an organization.
+//!
+use crate::client::RequestOptions;
+use crate::types::admin::api_keys::AdminError;
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait WorkspaceClient {
+    async fn list_workspaces<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> Result<ListWorkspacesResponse, AdminError>;
+
+    /// Stream all workspaces, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_workspaces_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+    ) -> impl Stream<Item = Result<Workspace, AdminError>> + 'a
+    where
+        Self: Sized;
+
+    async fn get_workspace<'a>(&'a self, workspace_id: &'a str) -> Result<Workspace, AdminError>;
+
+    async fn create_workspace<'a>(
+        &'a self,
+        params: &'a CreateWorkspaceParams,
+    ) -> Result<Workspace, AdminError>;
+
+    async fn update_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: &'a UpdateWorkspaceParams,
+    ) -> Result<Workspace, AdminError>;
+
+    async fn archive_workspace<'a>(
+        &'a self,
+        workspace_id: &'a str,
+    ) -> Result<Workspace, AdminError>;
+
+    /// Same as `list_workspaces`, with per-request options
+    ///
+    /// Defaults to `list_workspaces`, ignoring `options`.
+    async fn list_workspaces_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListWorkspacesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListWorkspacesResponse, AdminError> {
+        let _ = options;
+        self.list_workspaces(params).await
+    }
+
+    /// Same as `get_workspace`, with per-request options
+    ///
+    /// Defaults to `get_workspace`, ignoring `options`.
+    async fn get_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        let _ = options;
+        self.get_workspace(workspace_id).await
+    }
+
+    /// Same as `create_workspace`, with per-request options
+    ///
+    /// Defaults to `create_workspace`, ignoring `options`.
+    async fn create_workspace_with_options<'a>(
+        &'a self,
+        params: &'a CreateWorkspaceParams,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        let _ = options;
+        self.create_workspace(params).await
+    }
+
+    /// Same as `update_workspace`, with per-request options
+    ///
+    /// Defaults to `update_workspace`, ignoring `options`.
+    async fn update_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        params: &'a UpdateWorkspaceParams,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        let _ = options;
+        self.update_workspace(workspace_id, params).await
+    }
+
+    /// Same as `archive_workspace`, with per-request options
+    ///
+    /// Defaults to `archive_workspace`, ignoring `options`.
+    async fn archive_workspace_with_options<'a>(
+        &'a self,
+        workspace_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Workspace, AdminError> {
+        let _ = options;
+        self.archive_workspace(workspace_id).await
+    }
+}
+
+/// Parameters for listing workspaces
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct ListWorkspacesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+    /// Whether to include archived workspaces
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
+}
+
+impl ListWorkspacesParams {
+    /// Create a new ListWorkspacesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter (1-1000)
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.clamp(1, 1000));
+        self
+    }
+
+    /// Set the include_archived parameter
+    pub fn include_archived(mut self, include_archived: bool) -> Self {
+        self.include_archived = Some(include_archived);
+        self
+    }
+}
+
+/// Response structure for listing workspaces
+#[derive(Debug, Deserialize)]
+pub struct ListWorkspacesResponse {
+    /// List of workspaces
+    pub data: Vec<Workspace>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// Represents a workspace
+#[derive(Debug, Deserialize)]
+pub struct Workspace {
+    /// Unique identifier for the workspace
+    pub id: String,
+    /// Type of the resource (always "workspace")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Name of the workspace
+    pub name: String,
+    /// Creation timestamp
+    #[serde(with = "rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// Archival timestamp, if the workspace is archived
+    #[serde(with = "rfc3339::option")]
+    pub archived_at: Option<OffsetDateTime>,
+    /// Hex color code of the workspace in the Console
+    pub display_color: String,
+}
+
+/// Parameters for creating a workspace
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CreateWorkspaceParams {
+    /// Name of the workspace
+    pub name: String,
+}
+
+impl CreateWorkspaceParams {
+    /// Create a new CreateWorkspaceParams with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+/// Parameters for updating a workspace
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct UpdateWorkspaceParams {
+    /// New name of the workspace
+    pub name: String,
+}
+
+impl UpdateWorkspaceParams {
+    /// Create a new UpdateWorkspaceParams with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
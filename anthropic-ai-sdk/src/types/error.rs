@@ -0,0 +1,156 @@
+//! API Errors
+//!
+//! This module contains the types describing the error responses returned by the Anthropic API.
+//!
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Error object returned by the API
+///
+/// see https://docs.anthropic.com/en/api/errors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiErrorDetail {
+    /// Type of the error (e.g., "overloaded_error")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Human readable error message
+    pub message: String,
+}
+
+impl ApiErrorDetail {
+    /// Returns the HTTP status the API uses for this type of error
+    ///
+    /// Errors reported without a response status, such as the `error` events
+    /// of a stream, get their status from their type. Unknown types map to
+    /// 500.
+    pub fn status(&self) -> StatusCode {
+        match self.type_.as_str() {
+            "invalid_request_error" => StatusCode::BAD_REQUEST,
+            "authentication_error" => StatusCode::UNAUTHORIZED,
+            "permission_error" => StatusCode::FORBIDDEN,
+            "not_found_error" => StatusCode::NOT_FOUND,
+            "request_too_large" => StatusCode::PAYLOAD_TOO_LARGE,
+            "rate_limit_error" => StatusCode::TOO_MANY_REQUESTS,
+            "overloaded_error" => StatusCode::from_u16(529).unwrap(),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// Response body larger than the limit set with
+/// `AnthropicClientBuilder::with_max_response_bytes`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("Response body exceeds the limit of {limit} bytes")]
+pub struct ResponseTooLarge {
+    /// Maximum size of a response body, in bytes
+    pub limit: usize,
+}
+
+/// Envelope of an error response body
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    error: ApiErrorDetail,
+}
+
+/// Non-success response returned by the API
+#[derive(Debug, Clone)]
+pub struct ApiErrorResponse {
+    /// HTTP status code of the response
+    pub status: StatusCode,
+    /// Error object, if the body has the documented shape
+    pub error: Option<ApiErrorDetail>,
+    /// Raw response body
+    pub body: String,
+}
+
+impl ApiErrorResponse {
+    /// Create a new ApiErrorResponse from a status and a raw body
+    pub fn new(status: StatusCode, body: impl Into<String>) -> Self {
+        let body = body.into();
+        let error = serde_json::from_str::<ApiErrorBody>(&body)
+            .ok()
+            .map(|b| b.error);
+        Self {
+            status,
+            error,
+            body,
+        }
+    }
+
+    /// Create an ApiErrorResponse from an error object received without a
+    /// response status, such as in an `error` event of a stream
+    ///
+    /// The status is derived from the type of the error, see
+    /// [`ApiErrorDetail::status`].
+    pub fn from_detail(error: ApiErrorDetail) -> Self {
+        Self {
+            status: error.status(),
+            body: error.message.clone(),
+            error: Some(error),
+        }
+    }
+
+    /// Returns the type of the error (e.g., "not_found_error"), if known
+    pub fn error_type(&self) -> Option<&str> {
+        self.error.as_ref().map(|e| e.type_.as_str())
+    }
+
+    /// Returns the error message, falling back to the raw body
+    pub fn message(&self) -> &str {
+        self.error
+            .as_ref()
+            .map(|e| e.message.as_str())
+            .unwrap_or(&self.body)
+    }
+
+    /// Returns whether a request that failed this way is worth retrying
+    pub fn is_retryable(&self) -> bool {
+        Self::is_retryable_status(self.status)
+    }
+
+    /// Returns whether a response with the given status is worth retrying
+    ///
+    /// Request timeouts (408), lock conflicts (409), rate limits (429) and
+    /// server errors, including overloaded (529), are retryable.
+    pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(status.as_u16(), 408 | 409 | 429) || status.is_server_error()
+    }
+
+    /// Returns whether a request that failed without a response is worth retrying
+    ///
+    /// Timeouts and connection errors are retryable. Browsers do not tell
+    /// connection errors apart, so only timeouts are on wasm32.
+    pub(crate) fn is_retryable_error(error: &reqwest::Error) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        if error.is_connect() {
+            return true;
+        }
+        error.is_timeout()
+    }
+
+    /// Returns whether the error reports a deprecated or retired model
+    ///
+    /// Only a 400 `invalid_request_error` or a 404 `not_found_error` whose
+    /// message names a deprecated model counts. Other errors that happen to
+    /// mention one, such as a rate limit or an overloaded error, keep their
+    /// own kind.
+    pub fn is_model_deprecated(&self) -> bool {
+        let rejected = matches!(
+            (self.status.as_u16(), self.error_type()),
+            (400, Some("invalid_request_error")) | (404, Some("not_found_error"))
+        );
+        let message = self.message().to_lowercase();
+        rejected
+            && message.contains("model")
+            && [
+                "deprecated",
+                "retired",
+                "sunset",
+                "end-of-life",
+                "end of life",
+            ]
+            .iter()
+            .any(|keyword| message.contains(keyword))
+    }
+}
@@ -0,0 +1,279 @@
+//! Files API
+//!
+//! This module contains the types and functions for the Anthropic Files API.
+//!
+use crate::client::RequestOptions;
+use crate::types::error::{ApiErrorResponse, ResponseTooLarge};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::Stream;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use time::OffsetDateTime;
+use time::serde::rfc3339;
+
+/// Beta feature enabling the Files API, and file references in messages
+pub const FILES_API_BETA: &str = "files-api-2025-04-14";
+
+/// Error types for the Files API
+#[derive(Debug, Error)]
+pub enum FileError {
+    #[error("API request failed: {0}")]
+    RequestFailed(String),
+    #[error("API error: {0}")]
+    ApiError(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+    #[error("API error ({status}): {message}")]
+    ApiStatus { status: u16, message: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLarge),
+}
+
+impl From<String> for FileError {
+    fn from(error: String) -> Self {
+        FileError::ApiError(error)
+    }
+}
+
+impl From<ApiErrorResponse> for FileError {
+    fn from(error: ApiErrorResponse) -> Self {
+        match error.status.as_u16() {
+            401 => FileError::Unauthorized(error.message().to_string()),
+            403 => FileError::PermissionDenied(error.message().to_string()),
+            404 => FileError::NotFound(error.message().to_string()),
+            429 => FileError::RateLimited(error.message().to_string()),
+            529 => FileError::Overloaded(error.message().to_string()),
+            status => FileError::ApiStatus {
+                status,
+                message: error.message().to_string(),
+            },
+        }
+    }
+}
+
+impl FileError {
+    /// Returns the HTTP status code of the failed response, if there was one
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            FileError::Unauthorized(_) => Some(401),
+            FileError::PermissionDenied(_) => Some(403),
+            FileError::NotFound(_) => Some(404),
+            FileError::RateLimited(_) => Some(429),
+            FileError::Overloaded(_) => Some(529),
+            FileError::ApiStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the failed request is worth retrying
+    ///
+    /// Rate limits, overloaded and other server errors, and requests that
+    /// failed to send are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FileError::RequestFailed(_) | FileError::RateLimited(_) | FileError::Overloaded(_) => {
+                true
+            }
+            FileError::ApiStatus { status, .. } => {
+                StatusCode::from_u16(*status).is_ok_and(ApiErrorResponse::is_retryable_status)
+            }
+            FileError::Http(error) => ApiErrorResponse::is_retryable_error(error),
+            _ => false,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait FilesClient {
+    async fn upload_file<'a>(
+        &'a self,
+        data: Bytes,
+        filename: &'a str,
+        media_type: &'a str,
+    ) -> Result<FileMetadata, FileError>;
+
+    async fn list_files<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> Result<ListFilesResponse, FileError>;
+
+    /// Stream all files, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_files_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+    ) -> impl Stream<Item = Result<FileMetadata, FileError>> + 'a
+    where
+        Self: Sized;
+
+    async fn get_file_metadata<'a>(&'a self, file_id: &'a str) -> Result<FileMetadata, FileError>;
+
+    async fn delete_file<'a>(&'a self, file_id: &'a str) -> Result<FileDeleted, FileError>;
+
+    async fn download_file<'a>(&'a self, file_id: &'a str) -> Result<Bytes, FileError>;
+
+    /// Same as `upload_file`, with per-request options
+    ///
+    /// Defaults to `upload_file`, ignoring `options`.
+    async fn upload_file_with_options<'a>(
+        &'a self,
+        data: Bytes,
+        filename: &'a str,
+        media_type: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileMetadata, FileError> {
+        let _ = options;
+        self.upload_file(data, filename, media_type).await
+    }
+
+    /// Same as `list_files`, with per-request options
+    ///
+    /// Defaults to `list_files`, ignoring `options`.
+    async fn list_files_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListFilesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListFilesResponse, FileError> {
+        let _ = options;
+        self.list_files(params).await
+    }
+
+    /// Same as `get_file_metadata`, with per-request options
+    ///
+    /// Defaults to `get_file_metadata`, ignoring `options`.
+    async fn get_file_metadata_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileMetadata, FileError> {
+        let _ = options;
+        self.get_file_metadata(file_id).await
+    }
+
+    /// Same as `delete_file`, with per-request options
+    ///
+    /// Defaults to `delete_file`, ignoring `options`.
+    async fn delete_file_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<FileDeleted, FileError> {
+        let _ = options;
+        self.delete_file(file_id).await
+    }
+
+    /// Same as `download_file`, with per-request options
+    ///
+    /// Defaults to `download_file`, ignoring `options`.
+    async fn download_file_with_options<'a>(
+        &'a self,
+        file_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Bytes, FileError> {
+        let _ = options;
+        self.download_file(file_id).await
+    }
+}
+
+/// Parameters for listing files
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
+pub struct ListFilesParams {
+    /// Cursor for pagination (before)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// Cursor for pagination (after)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// Number of items per page (1-1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+impl ListFilesParams {
+    /// Create a new ListFilesParams with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the before_id parameter
+    pub fn before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    /// Set the after_id parameter
+    pub fn after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    /// Set the limit parameter (1-1000)
+    pub fn limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit.clamp(1, 1000));
+        self
+    }
+}
+
+/// Response structure for listing files
+#[derive(Debug, Deserialize)]
+pub struct ListFilesResponse {
+    /// List of files
+    pub data: Vec<FileMetadata>,
+    /// First ID in the data list
+    pub first_id: Option<String>,
+    /// Indicates if there are more results
+    pub has_more: bool,
+    /// Last ID in the data list
+    pub last_id: Option<String>,
+}
+
+/// Metadata of an uploaded file
+#[derive(Debug, Deserialize)]
+pub struct FileMetadata {
+    /// Unique identifier of the file, to reference it in content blocks
+    pub id: String,
+    /// Type of the resource (always "file")
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Original name of the file
+    pub filename: String,
+    /// Media type of the file, such as `application/pdf`
+    pub mime_type: String,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+    /// Upload timestamp
+    #[serde(with = "rfc3339")]
+    pub created_at: OffsetDateTime,
+    /// Whether the file can be downloaded, which is only the case for files
+    /// created by tools
+    #[serde(default)]
+    pub downloadable: bool,
+}
+
+/// Response of deleting a file
+#[derive(Debug, Deserialize)]
+pub struct FileDeleted {
+    /// ID of the deleted file
+    pub id: String,
+    /// Type of the resource (always "file_deleted")
+    #[serde(rename = "type")]
+    pub type_: String,
+}
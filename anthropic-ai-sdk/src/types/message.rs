@@ -1,6 +1,18 @@
+use crate::client::RequestOptions;
+use crate::types::error::{ApiErrorDetail, ApiErrorResponse, ResponseTooLarge};
 use async_trait::async_trait;
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use futures_util::{Stream, StreamExt};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use thiserror::Error;
+use time::OffsetDateTime;
+use time::serde::rfc3339;
 
 /// Error types for the Messages API
 #[derive(Debug, Error)]
@@ -9,6 +21,28 @@ pub enum MessageError {
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Model {model} is deprecated: {message}")]
+    ModelDeprecated { model: String, message: String },
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+    #[error("API error ({status}): {message}")]
+    ApiStatus { status: u16, message: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLarge),
 }
 
 impl From<String> for MessageError {
@@ -17,7 +51,76 @@ impl From<String> for MessageError {
     }
 }
 
-#[async_trait]
+impl From<ApiErrorResponse> for MessageError {
+    fn from(error: ApiErrorResponse) -> Self {
+        if error.is_model_deprecated() {
+            MessageError::ModelDeprecated {
+                model: String::new(),
+                message: error.message().to_string(),
+            }
+        } else {
+            match error.status.as_u16() {
+                401 => MessageError::Unauthorized(error.message().to_string()),
+                403 => MessageError::PermissionDenied(error.message().to_string()),
+                404 => MessageError::NotFound(error.message().to_string()),
+                429 => MessageError::RateLimited(error.message().to_string()),
+                529 => MessageError::Overloaded(error.message().to_string()),
+                status => MessageError::ApiStatus {
+                    status,
+                    message: error.message().to_string(),
+                },
+            }
+        }
+    }
+}
+
+impl MessageError {
+    /// Returns the HTTP status code of the failed response, if there was one
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            MessageError::Unauthorized(_) => Some(401),
+            MessageError::PermissionDenied(_) => Some(403),
+            MessageError::NotFound(_) => Some(404),
+            MessageError::RateLimited(_) => Some(429),
+            MessageError::Overloaded(_) => Some(529),
+            MessageError::ApiStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the failed request is worth retrying
+    ///
+    /// Rate limits, overloaded and other server errors, and requests that
+    /// failed to send are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            MessageError::RequestFailed(_)
+            | MessageError::RateLimited(_)
+            | MessageError::Overloaded(_) => true,
+            MessageError::ApiStatus { status, .. } => {
+                StatusCode::from_u16(*status).is_ok_and(ApiErrorResponse::is_retryable_status)
+            }
+            MessageError::Http(error) => ApiErrorResponse::is_retryable_error(error),
+            _ => false,
+        }
+    }
+}
+
+impl MessageError {
+    /// Fills in the model of a `ModelDeprecated` error from the request
+    pub(crate) fn with_model(self, model: &str) -> Self {
+        match self {
+            MessageError::ModelDeprecated { message, .. } => MessageError::ModelDeprecated {
+                model: model.to_string(),
+                message,
+            },
+            error => error,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait MessageClient {
     async fn create_message<'a>(
         &'a self,
@@ -32,13 +135,104 @@ pub trait MessageClient {
     async fn create_message_streaming<'a>(
         &'a self,
         body: &'a CreateMessageParams,
-    ) -> Result<
-        impl futures_util::Stream<Item = Result<StreamEvent, MessageError>> + 'a,
-        MessageError,
-    >;
+    ) -> Result<MessageStream, MessageError>;
+
+    /// Same as `create_message`, with per-request options
+    ///
+    /// Defaults to `create_message`, ignoring `options`.
+    async fn create_message_with_options<'a>(
+        &'a self,
+        params: Option<&'a CreateMessageParams>,
+        options: &'a RequestOptions,
+    ) -> Result<CreateMessageResponse, MessageError> {
+        let _ = options;
+        self.create_message(params).await
+    }
+
+    /// Same as `count_tokens`, with per-request options
+    ///
+    /// Defaults to `count_tokens`, ignoring `options`.
+    async fn count_tokens_with_options<'a>(
+        &'a self,
+        params: Option<&'a CountMessageTokensParams>,
+        options: &'a RequestOptions,
+    ) -> Result<CountMessageTokensResponse, MessageError> {
+        let _ = options;
+        self.count_tokens(params).await
+    }
+
+    /// Same as `create_message_streaming`, with per-request options
+    ///
+    /// Defaults to `create_message_streaming`, ignoring `options`.
+    async fn create_message_streaming_with_options<'a>(
+        &'a self,
+        body: &'a CreateMessageParams,
+        options: &'a RequestOptions,
+    ) -> Result<MessageStream, MessageError> {
+        let _ = options;
+        self.create_message_streaming(body).await
+    }
+
+    /// Create a message for each of the parameters, with at most
+    /// `concurrency` requests in flight
+    ///
+    /// Results are yielded in the order of `params`, whatever order the
+    /// responses arrive in. Each message is created with `create_message`, so
+    /// it is retried and rate limited the same way, and a failed request does
+    /// not stop the others. At least one request is sent at a time.
+    ///
+    /// Not available on `dyn MessageClient`, since the stream type is opaque.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use anthropic_ai_sdk::client::AnthropicClient;
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn example() -> Result<(), MessageError> {
+    /// let client = AnthropicClient::builder("your-api-key", "2023-06-01")
+    ///     .with_rate_limit(50)
+    ///     .build::<MessageError>()?;
+    ///
+    /// let prompts = ["What is Rust?", "What is Go?", "What is Zig?"];
+    /// let params = prompts.iter().map(|prompt| {
+    ///     CreateMessageParams::new(RequiredMessageParams {
+    ///         model: "claude-3-5-sonnet-20240620".to_string(),
+    ///         messages: vec![Message::user(*prompt)],
+    ///         max_tokens: 256,
+    ///     })
+    /// });
+    ///
+    /// let mut results = std::pin::pin!(client.create_messages_concurrent(params, 4));
+    /// while let Some(result) = results.next().await {
+    ///     match result {
+    ///         Ok(message) => println!("{}", message.text()),
+    ///         Err(e) => eprintln!("Error: {}", e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn create_messages_concurrent<'a, I>(
+        &'a self,
+        params: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<CreateMessageResponse, MessageError>> + 'a
+    where
+        Self: Sized,
+        I: IntoIterator<Item = CreateMessageParams>,
+        I::IntoIter: 'a,
+    {
+        futures_util::stream::iter(params)
+            .map(move |params| async move { self.create_message(Some(&params)).await })
+            .buffered(concurrency.max(1))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RequiredMessageParams {
     pub model: String,
     pub messages: Vec<Message>,
@@ -46,7 +240,32 @@ pub struct RequiredMessageParams {
 }
 
 /// Parameters for creating a message
-#[derive(Debug, Serialize, Default)]
+///
+/// The parameters can be cloned to send variations of a common request:
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{CreateMessageParams, Message, RequiredMessageParams};
+///
+/// let base = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-5-sonnet-20240620".to_string(),
+///     messages: Vec::new(),
+///     max_tokens: 1024,
+/// })
+/// .with_system("Answer in one sentence");
+///
+/// let requests: Vec<CreateMessageParams> = ["What is Rust?", "What is Go?"]
+///     .into_iter()
+///     .map(|question| {
+///         let mut params = base.clone();
+///         params.messages.push(Message::user(question));
+///         params
+///     })
+///     .collect();
+///
+/// assert_eq!(requests[0].system, base.system);
+/// assert_ne!(requests[0], requests[1]);
+/// ```
+#[derive(Debug, Serialize, Default, Clone, PartialEq)]
 pub struct CreateMessageParams {
     /// Maximum number of tokens to generate
     pub max_tokens: u32,
@@ -56,7 +275,7 @@ pub struct CreateMessageParams {
     pub model: String,
     /// System prompt
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPrompt>,
     /// Temperature for response generation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
@@ -84,6 +303,12 @@ pub struct CreateMessageParams {
     /// Request metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
+    /// Service tiers the request may be served with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<ServiceTier>,
+    /// ID of a code execution container to reuse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
 }
 
 impl From<RequiredMessageParams> for CreateMessageParams {
@@ -98,6 +323,15 @@ impl From<RequiredMessageParams> for CreateMessageParams {
 }
 
 impl CreateMessageParams {
+    /// Maximum number of images in a request
+    pub const MAX_IMAGES: usize = 100;
+
+    /// Maximum size of an inline image, in decoded bytes
+    pub const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+
+    /// Maximum size of a request, in bytes
+    pub const MAX_REQUEST_BYTES: usize = 32 * 1024 * 1024;
+
     /// Create new parameters with only required fields
     pub fn new(required: RequiredMessageParams) -> Self {
         required.into()
@@ -105,15 +339,82 @@ impl CreateMessageParams {
 
     // Builder methods for optional parameters
     pub fn with_system(mut self, system: impl Into<String>) -> Self {
-        self.system = Some(system.into());
+        self.system = Some(SystemPrompt::Text(system.into()));
         self
     }
 
+    /// Set the system prompt as text blocks, for example to mark a prefix as
+    /// cacheable with [`ContentBlock::cached`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, RequiredMessageParams,
+    /// };
+    /// use serde_json::json;
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-20240620".to_string(),
+    ///     messages: vec![Message::user("Summarize the contract")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_system_blocks(vec![
+    ///     ContentBlock::text("You are a lawyer reviewing the following contract.").cached(),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     params.to_wire_json()["system"],
+    ///     json!([{
+    ///         "type": "text",
+    ///         "text": "You are a lawyer reviewing the following contract.",
+    ///         "cache_control": { "type": "ephemeral" }
+    ///     }])
+    /// );
+    /// ```
+    pub fn with_system_blocks(mut self, blocks: Vec<ContentBlock>) -> Self {
+        self.system = Some(SystemPrompt::Blocks(blocks));
+        self
+    }
+
+    /// Set the temperature (0.0-1.0)
+    ///
+    /// Out of range values are rejected by `validate`.
     pub fn with_temperature(mut self, temperature: f32) -> Self {
         self.temperature = Some(temperature);
         self
     }
 
+    /// Set custom sequences that stop generation
+    ///
+    /// # Examples
+    ///
+    /// The stop sequences and the `top_k` and `top_p` sampling parameters are
+    /// only sent when set:
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{CreateMessageParams, Message, RequiredMessageParams};
+    /// use serde_json::json;
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-20240620".to_string(),
+    ///     messages: vec![Message::user("Count to ten")],
+    ///     max_tokens: 1024,
+    /// });
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// for field in ["stop_sequences", "top_k", "top_p"] {
+    ///     assert!(json.get(field).is_none(), "{field} sent while unset");
+    /// }
+    ///
+    /// let params = params
+    ///     .with_stop_sequences(vec!["five".to_string()])
+    ///     .with_top_k(40)
+    ///     .with_top_p(0.5);
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(json["stop_sequences"], json!(["five"]));
+    /// assert_eq!(json["top_k"], 40);
+    /// assert_eq!(json["top_p"], 0.5);
+    /// ```
     pub fn with_stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
         self.stop_sequences = Some(stop_sequences);
         self
@@ -129,6 +430,9 @@ impl CreateMessageParams {
         self
     }
 
+    /// Set the top-p threshold (0.0-1.0)
+    ///
+    /// Out of range values are rejected by `validate`.
     pub fn with_top_p(mut self, top_p: f32) -> Self {
         self.top_p = Some(top_p);
         self
@@ -149,14 +453,242 @@ impl CreateMessageParams {
         self
     }
 
+    /// Set the service tiers the request may be served with
+    pub fn with_service_tier(mut self, service_tier: ServiceTier) -> Self {
+        self.service_tier = Some(service_tier);
+        self
+    }
+
+    /// Reuse the code execution container of a previous response, see
+    /// `CreateMessageResponse::container`
+    ///
+    /// Files created by earlier code runs are still available in the reused
+    /// container.
+    pub fn with_container(mut self, container_id: impl Into<String>) -> Self {
+        self.container = Some(container_id.into());
+        self
+    }
+
     pub fn with_metadata(mut self, metadata: Metadata) -> Self {
         self.metadata = Some(metadata);
         self
     }
+
+    /// Set the `user_id` of the request metadata
+    pub fn with_metadata_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.metadata = Some(Metadata {
+            user_id: Some(user_id.into()),
+        });
+        self
+    }
+
+    /// Checks the parameters for mistakes the API would reject
+    ///
+    /// This verifies that there is at least one message, that `max_tokens` is
+    /// not zero, that `temperature` and `top_p` are between 0.0 and 1.0, that
+    /// no message has the role `Role::Other`, and that image and document
+    /// blocks only appear in user messages.
+    ///
+    /// It also checks the size limits of the API, tool results included: at
+    /// most `MAX_IMAGES` images, none larger than `MAX_IMAGE_BYTES` once
+    /// decoded, and at most `MAX_REQUEST_BYTES` of inline image and document
+    /// data. Sizes are estimated from the length of the base64 data.
+    ///
+    /// `create_message` calls it before sending the request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, MessageError, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let image = ContentBlock::image_base64("image/png", "iVBORw0KGgo=");
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![
+    ///         Message::new_text(Role::User, "Describe the picture"),
+    ///         Message::new_blocks(Role::Assistant, vec![image]),
+    ///     ],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// assert!(matches!(
+    ///     params.validate(),
+    ///     Err(MessageError::InvalidParameter(_))
+    /// ));
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![Message::new_text(Role::Other, "Hello")],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// assert!(matches!(
+    ///     params.validate(),
+    ///     Err(MessageError::InvalidParameter(_))
+    /// ));
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![Message::user("Hello")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_temperature(1.5);
+    ///
+    /// assert!(matches!(
+    ///     params.validate(),
+    ///     Err(MessageError::InvalidParameter(_))
+    /// ));
+    ///
+    /// let images = vec![ContentBlock::image_base64("image/png", "iVBORw0KGgo="); 101];
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![Message::new_blocks(Role::User, images)],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// assert!(matches!(
+    ///     params.validate(),
+    ///     Err(MessageError::InvalidParameter(_))
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), MessageError> {
+        if self.messages.is_empty() {
+            return Err(MessageError::InvalidParameter(
+                "messages must not be empty".to_string(),
+            ));
+        }
+        if self.max_tokens == 0 {
+            return Err(MessageError::InvalidParameter(
+                "max_tokens must be greater than 0".to_string(),
+            ));
+        }
+        for (name, value) in [("temperature", self.temperature), ("top_p", self.top_p)] {
+            if let Some(value) = value.filter(|v| !(0.0..=1.0).contains(v)) {
+                return Err(MessageError::InvalidParameter(format!(
+                    "{} must be between 0.0 and 1.0, got {}",
+                    name, value
+                )));
+            }
+        }
+        for (index, message) in self.messages.iter().enumerate() {
+            if message.role == Role::Other {
+                return Err(MessageError::InvalidParameter(format!(
+                    "messages[{}]: role must be \"user\" or \"assistant\"",
+                    index
+                )));
+            }
+            if !matches!(message.role, Role::Assistant) {
+                continue;
+            }
+            let MessageContent::Blocks { content } = &message.content else {
+                continue;
+            };
+            if let Some(block_type) = content.iter().find_map(ContentBlock::user_only_type) {
+                return Err(MessageError::InvalidParameter(format!(
+                    "messages[{}]: {} blocks are only allowed in user messages",
+                    index, block_type
+                )));
+            }
+        }
+        self.validate_sizes()
+    }
+
+    /// Checks the number and size of the images and documents
+    fn validate_sizes(&self) -> Result<(), MessageError> {
+        let mut images = 0;
+        let mut inline_bytes = 0;
+        for (index, message) in self.messages.iter().enumerate() {
+            let MessageContent::Blocks { content } = &message.content else {
+                continue;
+            };
+            for block in content.iter().flat_map(ContentBlock::with_nested) {
+                match block {
+                    ContentBlock::Image { source, .. } => {
+                        images += 1;
+                        if let ImageSource::Base64 { data, .. } = source {
+                            // Four base64 characters encode three bytes
+                            let size = data.len() / 4 * 3;
+                            if size > Self::MAX_IMAGE_BYTES {
+                                return Err(MessageError::InvalidParameter(format!(
+                                    "messages[{}]: image of about {} bytes exceeds the limit of {} bytes",
+                                    index,
+                                    size,
+                                    Self::MAX_IMAGE_BYTES
+                                )));
+                            }
+                            inline_bytes += data.len();
+                        }
+                    }
+                    ContentBlock::Document {
+                        source: DocumentSource::Base64 { data, .. },
+                        ..
+                    } => inline_bytes += data.len(),
+                    _ => {}
+                }
+            }
+        }
+        if images > Self::MAX_IMAGES {
+            return Err(MessageError::InvalidParameter(format!(
+                "{} images exceed the limit of {} per request",
+                images,
+                Self::MAX_IMAGES
+            )));
+        }
+        if inline_bytes > Self::MAX_REQUEST_BYTES {
+            return Err(MessageError::InvalidParameter(format!(
+                "{} bytes of inline images and documents exceed the request limit of {} bytes",
+                inline_bytes,
+                Self::MAX_REQUEST_BYTES
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns the JSON body that is sent for these parameters
+    ///
+    /// Useful for logging a request or attaching it to a bug report without
+    /// sending it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-latest".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_temperature(0.5);
+    ///
+    /// let json = params.to_wire_json();
+    /// assert_eq!(json["model"], "claude-3-5-sonnet-latest");
+    /// assert_eq!(json["max_tokens"], 1024);
+    /// assert_eq!(json["temperature"], 0.5);
+    /// assert_eq!(json["messages"][0]["content"], "Hello");
+    /// assert!(json.get("system").is_none());
+    /// ```
+    pub fn to_wire_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("CreateMessageParams always serializes to JSON")
+    }
+}
+
+/// System prompt, either plain text or a list of text blocks
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SystemPrompt {
+    /// Plain text
+    Text(String),
+    /// Text blocks, which can carry `cache_control`
+    Blocks(Vec<ContentBlock>),
 }
 
 /// Message in a conversation
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Message {
     /// Role of the message sender
     pub role: Role,
@@ -166,11 +698,84 @@ pub struct Message {
 }
 
 /// Role of a message sender
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+///
+/// Roles this SDK does not know are parsed as `Role::Other`, which is
+/// serialized back as `"other"`. `validate` rejects request messages with
+/// that role, so it is never sent to the API.
+///
+/// Strings are converted with `TryFrom`, which ignores case and rejects
+/// unknown roles instead of mapping them to `Role::Other`.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::Role;
+///
+/// let role: Role = serde_json::from_str(r#""assistant""#).unwrap();
+/// assert_eq!(role, Role::Assistant);
+/// assert_eq!(serde_json::to_string(&Role::User).unwrap(), r#""user""#);
+/// assert_eq!(
+///     serde_json::from_str::<Role>(&serde_json::to_string(&Role::User).unwrap()).unwrap(),
+///     Role::User
+/// );
+///
+/// let role: Role = serde_json::from_str(r#""system""#).unwrap();
+/// assert_eq!(role, Role::Other);
+///
+/// assert_eq!(Role::try_from("Assistant").unwrap(), Role::Assistant);
+/// assert_eq!(Role::try_from(String::from("user")).unwrap(), Role::User);
+/// assert!(Role::try_from("system").is_err());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Role {
     User,
     Assistant,
+    /// A role not known to this SDK
+    #[serde(other)]
+    Other,
+}
+
+impl Role {
+    /// Returns the role as it is written in JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Assistant => "assistant",
+            Role::Other => "other",
+        }
+    }
+}
+
+impl AsRef<str> for Role {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl TryFrom<&str> for Role {
+    type Error = MessageError;
+
+    fn try_from(role: &str) -> Result<Self, MessageError> {
+        if role.eq_ignore_ascii_case("user") {
+            Ok(Role::User)
+        } else if role.eq_ignore_ascii_case("assistant") {
+            Ok(Role::Assistant)
+        } else {
+            Err(MessageError::InvalidParameter(format!(
+                "unknown role {:?}, expected \"user\" or \"assistant\"",
+                role
+            )))
+        }
+    }
+}
+
+impl TryFrom<String> for Role {
+    type Error = MessageError;
+
+    fn try_from(role: String) -> Result<Self, MessageError> {
+        Role::try_from(role.as_str())
+    }
 }
 
 /// Content of a message
@@ -184,77 +789,563 @@ pub enum MessageContent {
 }
 
 /// Content block in a message
+///
+/// Used both to build request messages and to parse response content. Blocks
+/// with a `type` this SDK does not know (or that do not match the shape of a
+/// known type) are kept as [`ContentBlock::Unknown`] instead of failing the
+/// whole response.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-#[serde(tag = "type")]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
     /// Text content
-    #[serde(rename = "text")]
-    Text { text: String },
+    Text {
+        text: String,
+        /// Passages of the documents that back the text, in responses to
+        /// requests with citations enabled
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<Vec<Citation>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     /// Image content
-    #[serde(rename = "image")]
-    Image { source: ImageSource },
+    Image {
+        source: ImageSource,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
+    /// Document content, such as a PDF
+    Document {
+        source: DocumentSource,
+        /// Title of the document, shown to the model
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        title: Option<String>,
+        /// Context about the document that the model should not quote from
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        context: Option<String>,
+        /// Whether the model should cite the document in its response
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        citations: Option<CitationsConfig>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
+    },
     /// Tool use content
-    #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
         name: String,
         input: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Tool result content
-    #[serde(rename = "tool_result")]
     ToolResult {
         tool_use_id: String,
-        content: String,
+        content: ToolResultContent,
+        /// Whether the tool execution failed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        is_error: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cache_control: Option<CacheControl>,
     },
     /// Thinking content
-    #[serde(rename = "thinking")]
-    Thinking { thinking: String, signature: String },
+    ///
+    /// The block must be sent back unmodified, signature included, when
+    /// continuing a tool use conversation with extended thinking.
+    Thinking {
+        thinking: String,
+        /// Missing from the `content_block_start` event of a streamed block,
+        /// which receives it in a `signature_delta`
+        #[serde(default)]
+        signature: String,
+    },
     /// Redacted thinking
-    #[serde(rename = "redacted_thinking")]
     RedactedThinking { data: String },
+    /// Use of a tool run by the API itself, such as code execution
+    ///
+    /// Like thinking blocks, server tool blocks must be sent back unmodified
+    /// when continuing the conversation.
+    ServerToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    /// Result of running code with the code execution tool
+    CodeExecutionToolResult {
+        tool_use_id: String,
+        content: CodeExecutionResult,
+    },
+    /// Any other block, kept as raw JSON
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
 }
 
-/// Source of an image
+/// Outcome of a code execution, see `ContentBlock::CodeExecutionToolResult`
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{
+///     CodeExecutionResult, ContentBlock, CreateMessageResponse,
+/// };
+///
+/// let response: CreateMessageResponse = serde_json::from_str(
+///     r#"{
+///         "id": "msg_1",
+///         "type": "message",
+///         "role": "assistant",
+///         "model": "claude-sonnet-4-0",
+///         "content": [
+///             {
+///                 "type": "server_tool_use",
+///                 "id": "srvtoolu_1",
+///                 "name": "code_execution",
+///                 "input": { "code": "print(2 + 2)" }
+///             },
+///             {
+///                 "type": "code_execution_tool_result",
+///                 "tool_use_id": "srvtoolu_1",
+///                 "content": {
+///                     "type": "code_execution_result",
+///                     "stdout": "4\n",
+///                     "stderr": "",
+///                     "return_code": 0,
+///                     "content": []
+///                 }
+///             }
+///         ],
+///         "stop_reason": "end_turn",
+///         "stop_sequence": null,
+///         "usage": { "input_tokens": 10, "output_tokens": 20 },
+///         "container": { "id": "container_1", "expires_at": "2025-05-23T21:13:31.749448Z" }
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert!(matches!(
+///     &response.content[1],
+///     ContentBlock::CodeExecutionToolResult {
+///         content: CodeExecutionResult::CodeExecutionResult { stdout, return_code: 0, .. },
+///         ..
+///     } if stdout == "4\n"
+/// ));
+/// assert_eq!(response.container.unwrap().id, "container_1");
+/// ```
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
-pub struct ImageSource {
-    /// Type of image source
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeExecutionResult {
+    /// The code ran, successfully or not
+    CodeExecutionResult {
+        /// Standard output of the code
+        stdout: String,
+        /// Standard error of the code
+        stderr: String,
+        /// Exit code of the code, 0 on success
+        return_code: i32,
+        /// Files created by the code, which can be downloaded with the Files
+        /// API
+        #[serde(default)]
+        content: Vec<serde_json::Value>,
+    },
+    /// The code could not be run
+    CodeExecutionToolResultError {
+        /// Reason of the failure, such as `unavailable` or
+        /// `execution_time_exceeded`
+        error_code: String,
+    },
+}
+
+/// Content of a tool result
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    /// Plain text output
+    Text(String),
+    /// Content blocks, such as text and images
+    Blocks(Vec<ContentBlock>),
+}
+
+impl From<String> for ToolResultContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for ToolResultContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ContentBlock>> for ToolResultContent {
+    fn from(blocks: Vec<ContentBlock>) -> Self {
+        Self::Blocks(blocks)
+    }
+}
+
+/// Citations setting of a document block
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CitationsConfig {
+    /// Whether the model should cite the document
+    pub enabled: bool,
+}
+
+/// Passage of a document cited by a text block
+///
+/// `document_index` is the position of the cited document among the
+/// document blocks of the request.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{Citation, ContentBlock, CreateMessageResponse};
+///
+/// let json = serde_json::json!({
+///     "id": "msg_1",
+///     "type": "message",
+///     "role": "assistant",
+///     "model": "claude-3-5-sonnet-latest",
+///     "content": [
+///         { "type": "text", "text": "According to the report, " },
+///         {
+///             "type": "text",
+///             "text": "revenue grew 12%",
+///             "citations": [{
+///                 "type": "page_location",
+///                 "cited_text": "Revenue grew by 12% year over year.",
+///                 "document_index": 0,
+///                 "document_title": "Annual report",
+///                 "start_page_number": 3,
+///                 "end_page_number": 4
+///             }]
+///         }
+///     ],
+///     "stop_reason": "end_turn",
+///     "stop_sequence": null,
+///     "usage": { "input_tokens": 1200, "output_tokens": 25 }
+/// });
+///
+/// let response: CreateMessageResponse = serde_json::from_value(json.clone()).unwrap();
+/// let ContentBlock::Text { citations: Some(citations), .. } = &response.content[1] else {
+///     panic!("expected a cited text block");
+/// };
+/// assert!(matches!(
+///     &citations[0],
+///     Citation::PageLocation { start_page_number: 3, .. }
+/// ));
+///
+/// // Cited blocks are sent back unchanged on the next turn
+/// let content = serde_json::to_value(&response.content).unwrap();
+/// assert_eq!(content, json["content"]);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Citation {
+    /// Character range of a plain text document
+    CharLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        start_char_index: u32,
+        /// Exclusive end of the range
+        end_char_index: u32,
+    },
+    /// Page range of a PDF document
+    PageLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        /// First page, starting at 1
+        start_page_number: u32,
+        /// Exclusive end of the range
+        end_page_number: u32,
+    },
+    /// Range of blocks of a custom content document
+    ContentBlockLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        start_block_index: u32,
+        /// Exclusive end of the range
+        end_block_index: u32,
+    },
+    /// Any other citation, kept as raw JSON
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
+}
+
+/// Prompt caching marker
+///
+/// Marks the end of a cacheable prefix of the prompt. Everything up to and
+/// including the marked block is cached.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CacheControl {
     #[serde(rename = "type")]
-    pub type_: String,
-    /// Media type of the image
-    pub media_type: String,
-    /// Base64-encoded image data
-    pub data: String,
+    pub type_: CacheControlType,
+}
+
+impl CacheControl {
+    /// Create an ephemeral cache marker
+    pub fn ephemeral() -> Self {
+        Self {
+            type_: CacheControlType::Ephemeral,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CacheControlType {
+    #[serde(rename = "ephemeral")]
+    Ephemeral,
+}
+
+/// Source of an image
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// Image data sent inline
+    Base64 {
+        /// Media type of the image, such as `image/png`
+        media_type: String,
+        /// Base64-encoded image data
+        data: String,
+    },
+    /// Image fetched by the API from a URL
+    Url {
+        /// URL of the image
+        url: String,
+    },
+    /// Image uploaded with the Files API
+    File {
+        /// ID of the uploaded file
+        file_id: String,
+    },
+}
+
+/// Source of a document
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DocumentSource {
+    /// Document data sent inline
+    Base64 {
+        /// Media type of the document, such as `application/pdf`
+        media_type: String,
+        /// Base64-encoded document data
+        data: String,
+    },
+    /// Document fetched by the API from a URL
+    Url {
+        /// URL of the document
+        url: String,
+    },
+    /// Document uploaded with the Files API
+    File {
+        /// ID of the uploaded file
+        file_id: String,
+    },
 }
 
+/// Beta feature enabling the code execution tool, see `Tool::code_execution`
+pub const CODE_EXECUTION_BETA: &str = "code-execution-2025-05-22";
+
 /// Tool definition
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Tool {
+    /// Versioned type of a tool provided by the API, such as
+    /// `code_execution_20250522`; `None` for custom tools
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
     /// Name of the tool
     pub name: String,
     /// Description of the tool
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// JSON schema for tool input
+    /// JSON schema for tool input, left out for tools provided by the API
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub input_schema: serde_json::Value,
 }
 
+impl Tool {
+    /// Create the code execution tool, which lets the model run Python code
+    /// in a sandbox hosted by the API
+    ///
+    /// Requests using it must enable the [`CODE_EXECUTION_BETA`] beta. The
+    /// code and its output come back as `ContentBlock::ServerToolUse` and
+    /// `ContentBlock::CodeExecutionToolResult` blocks, and the sandbox can be
+    /// reused in the next request with `CreateMessageParams::with_container`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     CreateMessageParams, Message, RequiredMessageParams, Tool,
+    /// };
+    /// use serde_json::json;
+    ///
+    /// let params = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-sonnet-4-0".to_string(),
+    ///     messages: vec![Message::user("What is the standard deviation of 1, 2, 3, 4?")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_tools(vec![Tool::code_execution()])
+    /// .with_container("container_011CPR5CNjB747bTd36fQLFk");
+    ///
+    /// let json = serde_json::to_value(&params).unwrap();
+    /// assert_eq!(
+    ///     json["tools"],
+    ///     json!([{ "type": "code_execution_20250522", "name": "code_execution" }])
+    /// );
+    /// assert_eq!(json["container"], "container_011CPR5CNjB747bTd36fQLFk");
+    /// ```
+    pub fn code_execution() -> Self {
+        Self {
+            type_: Some("code_execution_20250522".to_string()),
+            name: "code_execution".to_string(),
+            description: None,
+            input_schema: serde_json::Value::Null,
+        }
+    }
+}
+
 /// Tool choice configuration
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// # Examples
+///
+/// Every variant is tagged with its `type` and reads back unchanged:
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::ToolChoice;
+/// use serde_json::json;
+///
+/// let cases = [
+///     (ToolChoice::auto(), json!({ "type": "auto" })),
+///     (
+///         ToolChoice::Any { disable_parallel_tool_use: Some(true) },
+///         json!({ "type": "any", "disable_parallel_tool_use": true }),
+///     ),
+///     (
+///         ToolChoice::tool("get_weather"),
+///         json!({ "type": "tool", "name": "get_weather" }),
+///     ),
+///     (ToolChoice::none(), json!({ "type": "none" })),
+/// ];
+/// for (choice, wire) in cases {
+///     assert_eq!(serde_json::to_value(&choice).unwrap(), wire);
+///     assert_eq!(serde_json::from_value::<ToolChoice>(wire).unwrap(), choice);
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum ToolChoice {
     /// Let model choose whether to use tools
     #[serde(rename = "auto")]
-    Auto,
+    Auto {
+        /// Whether to prevent the model from using several tools in parallel
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
     /// Model must use one of the provided tools
     #[serde(rename = "any")]
-    Any,
+    Any {
+        /// Whether to prevent the model from using several tools in parallel
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
     /// Model must use a specific tool
     #[serde(rename = "tool")]
-    Tool { name: String },
+    Tool {
+        /// Name of the tool to use
+        name: String,
+        /// Whether to prevent the model from using several tools in parallel
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        disable_parallel_tool_use: Option<bool>,
+    },
+    /// Model must not use any tools
+    #[serde(rename = "none")]
+    None,
+}
+
+impl ToolChoice {
+    /// Let the model choose whether to use tools
+    pub fn auto() -> Self {
+        Self::Auto {
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Make the model use one of the provided tools
+    pub fn any() -> Self {
+        Self::Any {
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Make the model use the named tool
+    pub fn tool(name: impl Into<String>) -> Self {
+        Self::Tool {
+            name: name.into(),
+            disable_parallel_tool_use: None,
+        }
+    }
+
+    /// Prevent the model from using any tools
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    /// Make the model use at most one tool (exactly one with `any` and `tool`)
+    ///
+    /// `None` does not use tools at all and is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::ToolChoice;
+    /// use serde_json::json;
+    ///
+    /// let to_json = |choice: ToolChoice| serde_json::to_value(choice).unwrap();
+    ///
+    /// assert_eq!(to_json(ToolChoice::auto()), json!({ "type": "auto" }));
+    /// assert_eq!(
+    ///     to_json(ToolChoice::auto().disable_parallel_tool_use()),
+    ///     json!({ "type": "auto", "disable_parallel_tool_use": true })
+    /// );
+    /// assert_eq!(to_json(ToolChoice::any()), json!({ "type": "any" }));
+    /// assert_eq!(
+    ///     to_json(ToolChoice::any().disable_parallel_tool_use()),
+    ///     json!({ "type": "any", "disable_parallel_tool_use": true })
+    /// );
+    /// assert_eq!(
+    ///     to_json(ToolChoice::tool("get_weather")),
+    ///     json!({ "type": "tool", "name": "get_weather" })
+    /// );
+    /// assert_eq!(
+    ///     to_json(ToolChoice::tool("get_weather").disable_parallel_tool_use()),
+    ///     json!({ "type": "tool", "name": "get_weather", "disable_parallel_tool_use": true })
+    /// );
+    /// assert_eq!(
+    ///     to_json(ToolChoice::none().disable_parallel_tool_use()),
+    ///     json!({ "type": "none" })
+    /// );
+    /// ```
+    pub fn disable_parallel_tool_use(mut self) -> Self {
+        match &mut self {
+            Self::Auto {
+                disable_parallel_tool_use,
+            }
+            | Self::Any {
+                disable_parallel_tool_use,
+            }
+            | Self::Tool {
+                disable_parallel_tool_use,
+                ..
+            } => *disable_parallel_tool_use = Some(true),
+            Self::None => {}
+        }
+        self
+    }
 }
 
 /// Configuration for extended thinking
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 pub struct Thinking {
     /// Must be at least 1024 tokens
     pub budget_tokens: usize,
@@ -262,17 +1353,46 @@ pub struct Thinking {
     pub type_: ThinkingType,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 pub enum ThinkingType {
     #[serde(rename = "enabled")]
-    Enabled
+    Enabled,
 }
+
 /// Message metadata
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
 pub struct Metadata {
-    /// Custom metadata fields
-    #[serde(flatten)]
-    pub fields: std::collections::HashMap<String, String>,
+    /// External identifier of the end user, such as a hash of their ID
+    ///
+    /// Must not contain identifying information like names, emails or phone
+    /// numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+/// Service tiers a request may be served with
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceTier {
+    /// Use priority capacity when available, standard capacity otherwise
+    Auto,
+    /// Only use standard capacity
+    StandardOnly,
+}
+
+/// Service tier a request was actually served with
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsedServiceTier {
+    /// Standard capacity
+    Standard,
+    /// Priority capacity
+    Priority,
+    /// Message Batches API
+    Batch,
+    /// A tier this version of the SDK does not know about
+    #[serde(other)]
+    Unknown,
 }
 
 /// Response from creating a message
@@ -295,34 +1415,202 @@ pub struct CreateMessageResponse {
     pub type_: String,
     /// Usage statistics
     pub usage: Usage,
+    /// Code execution container used by the request, to reuse with
+    /// `CreateMessageParams::with_container`
+    #[serde(default)]
+    pub container: Option<Container>,
+}
+
+/// Code execution container, see `Tool::code_execution`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Container {
+    /// ID of the container
+    pub id: String,
+    /// Time after which the container is deleted
+    #[serde(with = "rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl CreateMessageResponse {
+    /// Returns the service tier the request was served with, as reported in
+    /// the usage statistics
+    pub fn service_tier(&self) -> Option<UsedServiceTier> {
+        self.usage.service_tier
+    }
+
+    /// Returns the concatenated text of all text blocks
+    ///
+    /// Thinking, redacted thinking and tool blocks are not text and are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_value(serde_json::json!({
+    ///     "id": "msg_123",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-sonnet-4-0",
+    ///     "content": [
+    ///         { "type": "thinking", "thinking": "The user greets me. ", "signature": "sig" },
+    ///         { "type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix" },
+    ///         { "type": "text", "text": "Hello" },
+    ///         { "type": "thinking", "thinking": "Add a question.", "signature": "sig" },
+    ///         { "type": "text", "text": ", how can I help?" }
+    ///     ],
+    ///     "stop_reason": "end_turn",
+    ///     "stop_sequence": null,
+    ///     "usage": { "input_tokens": 10, "output_tokens": 20 }
+    /// }))?;
+    ///
+    /// assert_eq!(response.text(), "Hello, how can I help?");
+    /// assert_eq!(response.thinking_text(), "The user greets me. Add a question.");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the concatenated text of the visible thinking blocks
+    ///
+    /// Redacted thinking blocks are encrypted and are skipped.
+    pub fn thinking_text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::Thinking { thinking, .. } => Some(thinking.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the tool use blocks, in order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::CreateMessageResponse;
+    ///
+    /// let response: CreateMessageResponse = serde_json::from_value(serde_json::json!({
+    ///     "id": "msg_123",
+    ///     "type": "message",
+    ///     "role": "assistant",
+    ///     "model": "claude-3-5-sonnet-latest",
+    ///     "content": [
+    ///         { "type": "text", "text": "Let me check." },
+    ///         { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "Paris" } }
+    ///     ],
+    ///     "stop_reason": "tool_use",
+    ///     "stop_sequence": null,
+    ///     "usage": { "input_tokens": 10, "output_tokens": 20 }
+    /// }))?;
+    ///
+    /// assert_eq!(response.text(), "Let me check.");
+    /// let tool_uses = response.tool_uses();
+    /// assert_eq!(tool_uses.len(), 1);
+    /// assert_eq!(tool_uses[0].name, "get_weather");
+    /// assert_eq!(tool_uses[0].input["city"], "Paris");
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn tool_uses(&self) -> Vec<ToolUseBlock<'_>> {
+        self.content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::ToolUse {
+                    id, name, input, ..
+                } => Some(ToolUseBlock { id, name, input }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Borrowed view of a tool use block of a response
+#[derive(Debug, Clone, Copy)]
+pub struct ToolUseBlock<'a> {
+    /// ID to reference in the matching tool result
+    pub id: &'a str,
+    /// Name of the tool to call
+    pub name: &'a str,
+    /// Input of the tool call
+    pub input: &'a serde_json::Value,
 }
 
 /// Reason for stopping message generation
-#[derive(Debug, Deserialize)]
+///
+/// Reasons added to the API after this version of the crate deserialize as
+/// `Unknown` instead of failing the whole response.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum StopReason {
+    /// The model reached a natural stopping point
     EndTurn,
+    /// The response reached `max_tokens`
     MaxTokens,
+    /// The model generated one of the custom stop sequences
     StopSequence,
+    /// The model wants to use a tool
     ToolUse,
+    /// A stop reason not known to this version of the crate
+    #[serde(other)]
+    Unknown,
 }
 
 /// Token usage statistics
-#[derive(Debug, Deserialize)]
+///
+/// Token counts default to zero (and cache counts to `None`) when absent, so
+/// the partial usage objects sent in streaming `message_delta` events
+/// deserialize cleanly.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Usage {
     /// Input tokens used
+    #[serde(default)]
     pub input_tokens: u32,
     /// Output tokens used
+    #[serde(default)]
     pub output_tokens: u32,
+    /// Input tokens written to the prompt cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Input tokens read from the prompt cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<u32>,
+    /// Service tier the request was served with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<UsedServiceTier>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Usage {
+    /// Returns all input tokens, whether uncached, read from or written to the cache
+    pub fn total_input_tokens(&self) -> u32 {
+        self.input_tokens
+            + self.cache_creation_input_tokens.unwrap_or(0)
+            + self.cache_read_input_tokens.unwrap_or(0)
+    }
+}
+
+/// Cumulative token usage sent in `message_delta` events
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
 pub struct StreamUsage {
     /// Input tokens used (may be missing in some events)
     #[serde(default)]
     pub input_tokens: u32,
-    /// Output tokens used
+    /// Output tokens used (may be missing in some events)
+    #[serde(default)]
     pub output_tokens: u32,
+    /// Input tokens written to the prompt cache (may be missing in some events)
+    #[serde(default)]
+    pub cache_creation_input_tokens: Option<u32>,
+    /// Input tokens read from the prompt cache (may be missing in some events)
+    #[serde(default)]
+    pub cache_read_input_tokens: Option<u32>,
 }
 
 impl Message {
@@ -337,41 +1625,539 @@ impl Message {
     }
 
     /// Create a new message with content blocks
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{
+    ///     ContentBlock, CreateMessageParams, Message, RequiredMessageParams, Role,
+    /// };
+    ///
+    /// let params_with_options = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-20240620".to_string(),
+    ///     messages: vec![Message::new_text(Role::User, "Hello, Claude")],
+    ///     max_tokens: 1024,
+    /// })
+    /// .with_temperature(0.7)
+    /// .with_system("You are a helpful assistant");
+    ///
+    /// let params_with_blocks = CreateMessageParams::new(RequiredMessageParams {
+    ///     model: "claude-3-5-sonnet-20240620".to_string(),
+    ///     messages: vec![Message::new_blocks(
+    ///         Role::User,
+    ///         vec![
+    ///             ContentBlock::image_base64("image/png", "iVBORw0KGgo="),
+    ///             ContentBlock::text("What is in this image?"),
+    ///         ],
+    ///     )],
+    ///     max_tokens: 1024,
+    /// });
+    ///
+    /// let json = serde_json::to_value(&params_with_blocks).unwrap();
+    /// assert_eq!(json["messages"][0]["content"][0]["type"], "image");
+    /// assert_eq!(json["messages"][0]["content"][1]["text"], "What is in this image?");
+    /// # assert_eq!(params_with_options.temperature, Some(0.7));
+    /// ```
     pub fn new_blocks(role: Role, blocks: Vec<ContentBlock>) -> Self {
         Self {
             role,
             content: MessageContent::Blocks { content: blocks },
         }
     }
+
+    /// Create a new user message with simple text content
+    pub fn user(text: impl Into<String>) -> Self {
+        Self::new_text(Role::User, text)
+    }
+
+    /// Create a new assistant message with simple text content
+    pub fn assistant(text: impl Into<String>) -> Self {
+        Self::new_text(Role::Assistant, text)
+    }
+}
+
+/// Builder of the messages of a multi-turn conversation
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{Conversation, Message};
+///
+/// let messages: Vec<Message> = Conversation::new()
+///     .user("What is the weather in Paris?")
+///     .assistant("Let me check.")
+///     .tool_result("toolu_1", "18°C and sunny")
+///     .tool_result("toolu_2", "Light wind")
+///     .into();
+///
+/// // Consecutive tool results share one user message
+/// assert_eq!(messages.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Conversation {
+    messages: Vec<Message>,
+}
+
+impl Conversation {
+    /// Create a new empty conversation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a user message
+    pub fn user(mut self, text: impl Into<String>) -> Self {
+        self.push(Message::user(text));
+        self
+    }
+
+    /// Add an assistant message
+    pub fn assistant(mut self, text: impl Into<String>) -> Self {
+        self.push(Message::assistant(text));
+        self
+    }
+
+    /// Add the result of a tool call
+    ///
+    /// The result is appended to the last message when it is a user message
+    /// made of content blocks, so that the results of parallel tool calls are
+    /// sent together.
+    pub fn tool_result(
+        self,
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        self.push_tool_result(ContentBlock::tool_result(tool_use_id, content))
+    }
+
+    /// Add the result of a tool call that failed
+    ///
+    /// Grouped with the other tool results like `tool_result`.
+    pub fn tool_error(
+        self,
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        self.push_tool_result(ContentBlock::tool_error(tool_use_id, content))
+    }
+
+    fn push_tool_result(mut self, block: ContentBlock) -> Self {
+        match self.messages.last_mut() {
+            Some(Message {
+                role: Role::User,
+                content: MessageContent::Blocks { content },
+            }) => content.push(block),
+            _ => self.push(Message::new_blocks(Role::User, vec![block])),
+        }
+        self
+    }
+
+    /// Add a message, such as the content of a response
+    pub fn push(&mut self, message: Message) {
+        self.messages.push(message);
+    }
+
+    /// Returns the messages of the conversation
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+}
+
+impl From<Conversation> for Vec<Message> {
+    fn from(conversation: Conversation) -> Self {
+        conversation.messages
+    }
 }
 
 // Helper methods for content blocks
 impl ContentBlock {
     /// Create a new text block
     pub fn text(text: impl Into<String>) -> Self {
-        Self::Text { text: text.into() }
+        Self::Text {
+            text: text.into(),
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new tool result block
+    ///
+    /// The content is either text or a list of blocks, such as an image
+    /// produced by the tool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::ContentBlock;
+    /// use serde_json::json;
+    ///
+    /// let text = ContentBlock::tool_result("toolu_1", "18°C and sunny");
+    /// let json = serde_json::to_value(&text).unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     json!({ "type": "tool_result", "tool_use_id": "toolu_1", "content": "18°C and sunny" })
+    /// );
+    /// assert_eq!(serde_json::from_value::<ContentBlock>(json).unwrap(), text);
+    ///
+    /// let blocks = ContentBlock::tool_result(
+    ///     "toolu_2",
+    ///     vec![
+    ///         ContentBlock::text("Here is the chart"),
+    ///         ContentBlock::image_base64("image/png", "iVBORw0KGgo="),
+    ///     ],
+    /// );
+    /// let json = serde_json::to_value(&blocks).unwrap();
+    /// assert_eq!(
+    ///     json,
+    ///     json!({
+    ///         "type": "tool_result",
+    ///         "tool_use_id": "toolu_2",
+    ///         "content": [
+    ///             { "type": "text", "text": "Here is the chart" },
+    ///             {
+    ///                 "type": "image",
+    ///                 "source": { "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo=" }
+    ///             }
+    ///         ]
+    ///     })
+    /// );
+    /// assert_eq!(serde_json::from_value::<ContentBlock>(json).unwrap(), blocks);
+    ///
+    /// let error = ContentBlock::tool_error("toolu_3", "city not found");
+    /// assert_eq!(serde_json::to_value(&error).unwrap()["is_error"], true);
+    /// ```
+    pub fn tool_result(
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        Self::ToolResult {
+            tool_use_id: tool_use_id.into(),
+            content: content.into(),
+            is_error: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new tool result block reporting that the tool failed
+    pub fn tool_error(
+        tool_use_id: impl Into<String>,
+        content: impl Into<ToolResultContent>,
+    ) -> Self {
+        Self::ToolResult {
+            tool_use_id: tool_use_id.into(),
+            content: content.into(),
+            is_error: Some(true),
+            cache_control: None,
+        }
     }
 
     /// Create a new image block
+    ///
+    /// The source type is implied by the constructor now, so `type_` is ignored.
+    #[deprecated(note = "use `ContentBlock::image_base64` or `ContentBlock::image_url` instead")]
     pub fn image(
-        type_: impl Into<String>,
+        _type_: impl Into<String>,
         media_type: impl Into<String>,
         data: impl Into<String>,
     ) -> Self {
+        Self::image_base64(media_type, data)
+    }
+
+    /// Create a new image block from base64-encoded data
+    pub fn image_base64(media_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::Image {
+            source: ImageSource::Base64 {
+                media_type: media_type.into(),
+                data: data.into(),
+            },
+            cache_control: None,
+        }
+    }
+
+    /// Create a new image block that the API fetches from a URL
+    pub fn image_url(url: impl Into<String>) -> Self {
         Self::Image {
-            source: ImageSource {
-                type_: type_.into(),
+            source: ImageSource::Url { url: url.into() },
+            cache_control: None,
+        }
+    }
+
+    /// Create a new image block referencing a file uploaded with the Files API
+    ///
+    /// The message must be sent with the `files-api-2025-04-14` beta enabled.
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        Self::Image {
+            source: ImageSource::File {
+                file_id: file_id.into(),
+            },
+            cache_control: None,
+        }
+    }
+
+    /// Create a new image block from a file
+    ///
+    /// The media type is inferred from the extension, which must be one of
+    /// `jpg`, `jpeg`, `png`, `gif` or `webp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or its extension is not a
+    /// supported image type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::ContentBlock;
+    ///
+    /// let path = std::env::temp_dir().join("anthropic-ai-sdk-doctest.png");
+    /// std::fs::write(&path, b"\x89PNG\r\n\x1a\n")?;
+    ///
+    /// let image = ContentBlock::image_from_path(&path)?;
+    /// assert_eq!(
+    ///     serde_json::to_value(&image)?,
+    ///     serde_json::json!({
+    ///         "type": "image",
+    ///         "source": {
+    ///             "type": "base64",
+    ///             "media_type": "image/png",
+    ///             "data": "iVBORw0KGgo=",
+    ///         },
+    ///     })
+    /// );
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn image_from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let media_type = match extension.as_deref() {
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("png") => "image/png",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("unsupported image type: {}", path.display()),
+                ));
+            }
+        };
+        let data = std::fs::read(path)?;
+        Ok(Self::image_base64(media_type, BASE64_STANDARD.encode(data)))
+    }
+
+    /// Create a new document block from base64-encoded data
+    pub fn document_base64(media_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::Document {
+            source: DocumentSource::Base64 {
                 media_type: media_type.into(),
                 data: data.into(),
             },
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new document block that the API fetches from a URL
+    pub fn document_url(url: impl Into<String>) -> Self {
+        Self::Document {
+            source: DocumentSource::Url { url: url.into() },
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new document block referencing a file uploaded with the Files
+    /// API
+    ///
+    /// The message must be sent with the `files-api-2025-04-14` beta enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::ContentBlock;
+    /// use serde_json::json;
+    ///
+    /// let block = ContentBlock::document_file("file_011CNha8iCJcU1wXNR6q4V8w");
+    /// assert_eq!(
+    ///     serde_json::to_value(&block).unwrap(),
+    ///     json!({
+    ///         "type": "document",
+    ///         "source": { "type": "file", "file_id": "file_011CNha8iCJcU1wXNR6q4V8w" }
+    ///     })
+    /// );
+    /// ```
+    pub fn document_file(file_id: impl Into<String>) -> Self {
+        Self::Document {
+            source: DocumentSource::File {
+                file_id: file_id.into(),
+            },
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// Create a new PDF document block from a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not have a `pdf`
+    /// extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::ContentBlock;
+    ///
+    /// let path = std::env::temp_dir().join("anthropic-ai-sdk-doctest.pdf");
+    /// std::fs::write(&path, b"%PDF-1.4")?;
+    ///
+    /// let document = ContentBlock::document_from_path(&path)?;
+    /// assert_eq!(
+    ///     serde_json::to_value(&document)?,
+    ///     serde_json::json!({
+    ///         "type": "document",
+    ///         "source": {
+    ///             "type": "base64",
+    ///             "media_type": "application/pdf",
+    ///             "data": "JVBERi0xLjQ=",
+    ///         },
+    ///     })
+    /// );
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn document_from_path(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let is_pdf = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"));
+        if !is_pdf {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("unsupported document type: {}", path.display()),
+            ));
+        }
+        let data = std::fs::read(path)?;
+        Ok(Self::document_base64(
+            "application/pdf",
+            BASE64_STANDARD.encode(data),
+        ))
+    }
+
+    /// Mark this block as the end of a cacheable prompt prefix
+    ///
+    /// Thinking and unknown blocks cannot be cached and are returned unchanged.
+    pub fn cached(mut self) -> Self {
+        match &mut self {
+            Self::Text { cache_control, .. }
+            | Self::Image { cache_control, .. }
+            | Self::Document { cache_control, .. }
+            | Self::ToolUse { cache_control, .. }
+            | Self::ToolResult { cache_control, .. } => {
+                *cache_control = Some(CacheControl::ephemeral());
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// Let the model cite this document in its response
+    ///
+    /// Blocks other than documents are returned unchanged.
+    pub fn with_citations(mut self) -> Self {
+        if let Self::Document { citations, .. } = &mut self {
+            *citations = Some(CitationsConfig { enabled: true });
+        }
+        self
+    }
+
+    /// Returns the block followed by the blocks nested in it, which are the
+    /// content of a tool result
+    fn with_nested(&self) -> impl Iterator<Item = &ContentBlock> {
+        let nested = match self {
+            Self::ToolResult {
+                content: ToolResultContent::Blocks(blocks),
+                ..
+            } => blocks.as_slice(),
+            _ => &[],
+        };
+        std::iter::once(self).chain(nested)
+    }
+
+    /// Returns the type of blocks that may only be sent in user messages
+    fn user_only_type(&self) -> Option<&str> {
+        match self {
+            Self::Image { .. } => Some("image"),
+            Self::Document { .. } => Some("document"),
+            _ => None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq)]
 pub struct CountMessageTokensParams {
     pub model: String,
     pub messages: Vec<Message>,
+    /// System prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPrompt>,
+    /// Tools that the model may use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<Tool>>,
+    /// Configuration for enabling Claude's extended thinking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+}
+
+impl CountMessageTokensParams {
+    pub fn with_system(mut self, system: impl Into<String>) -> Self {
+        self.system = Some(SystemPrompt::Text(system.into()));
+        self
+    }
+
+    /// Set the system prompt as text blocks
+    pub fn with_system_blocks(mut self, blocks: Vec<ContentBlock>) -> Self {
+        self.system = Some(SystemPrompt::Blocks(blocks));
+        self
+    }
+
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    pub fn with_thinking(mut self, thinking: Thinking) -> Self {
+        self.thinking = Some(thinking);
+        self
+    }
+
+    /// Checks the parameters for mistakes the API would reject
+    ///
+    /// This verifies that there is at least one message. `count_tokens` calls
+    /// it before sending the request.
+    pub fn validate(&self) -> Result<(), MessageError> {
+        if self.messages.is_empty() {
+            return Err(MessageError::InvalidParameter(
+                "messages must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the JSON body that is sent for these parameters
+    pub fn to_wire_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("CountMessageTokensParams always serializes to JSON")
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -379,6 +2165,11 @@ pub struct CountMessageTokensResponse {
     pub input_tokens: u32,
 }
 
+/// Event of a streaming Messages API response
+///
+/// Events with a `type` this SDK does not know (or that do not match the
+/// shape of a known type) are kept as [`StreamEvent::Unknown`] so that new
+/// server events never break existing consumers.
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum StreamEvent {
@@ -403,10 +2194,19 @@ pub enum StreamEvent {
     },
     #[serde(rename = "message_stop")]
     MessageStop,
+    /// Keep-alive event, skipped by `parse_sse_stream`
     #[serde(rename = "ping")]
     Ping,
+    /// Error reported in the middle of the stream, such as
+    /// `overloaded_error`
+    ///
+    /// `parse_sse_stream` returns it as a `MessageError` instead, mapped from
+    /// the type of the error like an error response would be.
     #[serde(rename = "error")]
     Error { error: StreamError },
+    /// Any other event, kept as raw JSON
+    #[serde(untagged)]
+    Unknown(serde_json::Value),
 }
 
 #[derive(Debug, Deserialize)]
@@ -420,6 +2220,8 @@ pub struct MessageStartContent {
     pub stop_reason: Option<StopReason>,
     pub stop_sequence: Option<String>,
     pub usage: Usage,
+    #[serde(default)]
+    pub container: Option<Container>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -433,17 +2235,437 @@ pub enum ContentBlockDelta {
     ThinkingDelta { thinking: String },
     #[serde(rename = "signature_delta")]
     SignatureDelta { signature: String },
+    #[serde(rename = "citations_delta")]
+    CitationsDelta { citation: Citation },
 }
 
 #[derive(Debug, Deserialize)]
 pub struct MessageDeltaContent {
     pub stop_reason: Option<StopReason>,
     pub stop_sequence: Option<String>,
+    #[serde(default)]
+    pub container: Option<Container>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct StreamError {
-    #[serde(rename = "type")]
-    pub type_: String,
-    pub message: String,
-}
\ No newline at end of file
+/// Error object of an `error` stream event
+pub type StreamError = ApiErrorDetail;
+
+/// Events of a streaming response, returned by
+/// `MessageClient::create_message_streaming`
+///
+/// It implements `Stream`, and also has an inherent `next` method so that
+/// events can be read without importing `StreamExt`. Being a named type, it
+/// can be stored in a struct field.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams,
+/// };
+///
+/// # async fn example() -> Result<(), MessageError> {
+/// let client = AnthropicClient::new::<MessageError>("your-api-key", "2023-06-01")?;
+/// let body = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-5-sonnet-latest".to_string(),
+///     messages: vec![Message::user("Hello, Claude")],
+///     max_tokens: 1024,
+/// })
+/// .with_stream(true);
+///
+/// let mut stream = client.create_message_streaming(&body).await?;
+/// while let Some(event) = stream.next().await {
+///     println!("{:?}", event?);
+/// }
+///
+/// let text = client
+///     .create_message_streaming(&body)
+///     .await?
+///     .collect_text()
+///     .await?;
+/// println!("{text}");
+/// # Ok(())
+/// # }
+/// ```
+pub struct MessageStream {
+    inner: EventStream,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type EventStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, MessageError>> + Send>>;
+// Browser responses cannot be sent to another thread
+#[cfg(target_arch = "wasm32")]
+type EventStream = Pin<Box<dyn Stream<Item = Result<StreamEvent, MessageError>>>>;
+
+impl MessageStream {
+    /// Wraps a stream of events
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(
+        stream: impl Stream<Item = Result<StreamEvent, MessageError>> + Send + 'static,
+    ) -> Self {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+
+    /// Wraps a stream of events
+    #[cfg(target_arch = "wasm32")]
+    pub fn new(stream: impl Stream<Item = Result<StreamEvent, MessageError>> + 'static) -> Self {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+
+    /// Returns the next event, or `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<Result<StreamEvent, MessageError>> {
+        StreamExt::next(&mut self.inner).await
+    }
+
+    /// Reads the whole stream and returns the text of the message, see
+    /// `CreateMessageResponse::text`
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the stream, or an error if the events do
+    /// not form a complete message, see `MessageAccumulator`.
+    pub async fn collect_text(self) -> Result<String, MessageError> {
+        Ok(MessageAccumulator::accumulate(self).await?.text())
+    }
+}
+
+impl Stream for MessageStream {
+    type Item = Result<StreamEvent, MessageError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl fmt::Debug for MessageStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageStream").finish_non_exhaustive()
+    }
+}
+
+/// Reassembles a complete message from the events of a streaming response
+///
+/// Text deltas are appended to their text block and `input_json_delta`
+/// fragments are collected and parsed into the tool's `input` once the block
+/// stops. Thinking deltas are appended to their thinking block, which also
+/// receives its signature, so that it can be sent back on the next turn. The
+/// stop reason and usage are available as soon as the `message_delta` event
+/// has been pushed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anthropic_ai_sdk::client::AnthropicClient;
+/// use anthropic_ai_sdk::types::message::{
+///     CreateMessageParams, Message, MessageAccumulator, MessageClient, MessageError,
+///     RequiredMessageParams, Role,
+/// };
+///
+/// # async fn example() -> Result<(), MessageError> {
+/// let client = AnthropicClient::new::<MessageError>("your-api-key", "2023-06-01")?;
+/// let body = CreateMessageParams::new(RequiredMessageParams {
+///     model: "claude-3-5-sonnet-latest".to_string(),
+///     messages: vec![Message::new_text(Role::User, "Hello, Claude")],
+///     max_tokens: 1024,
+/// })
+/// .with_stream(true);
+///
+/// let stream = client.create_message_streaming(&body).await?;
+/// let message = MessageAccumulator::accumulate(stream).await?;
+/// println!("{}", message.text());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Reconstructing a thinking block from recorded events:
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{ContentBlock, MessageAccumulator, StreamEvent};
+///
+/// let events = [
+///     r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-0","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#,
+///     r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me add "}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"2 and 2."}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"EqQBCgIYAhIM"}}"#,
+///     r#"{"type":"content_block_stop","index":0}"#,
+///     r#"{"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#,
+///     r#"{"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"4"}}"#,
+///     r#"{"type":"content_block_stop","index":1}"#,
+///     r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"output_tokens":20}}"#,
+///     r#"{"type":"message_stop"}"#,
+/// ];
+///
+/// let mut accumulator = MessageAccumulator::new();
+/// for event in events {
+///     accumulator.push(serde_json::from_str::<StreamEvent>(event).unwrap()).unwrap();
+/// }
+/// let message = accumulator.finish().unwrap();
+///
+/// assert_eq!(message.thinking_text(), "Let me add 2 and 2.");
+/// assert!(matches!(
+///     &message.content[0],
+///     ContentBlock::Thinking { signature, .. } if signature == "EqQBCgIYAhIM"
+/// ));
+/// assert_eq!(message.text(), "4");
+/// ```
+///
+/// Reassembling tool inputs streamed as fragments of JSON:
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::{ContentBlock, MessageAccumulator, StreamEvent};
+/// use serde_json::json;
+///
+/// let events = [
+///     r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-0","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#,
+///     r#"{"type":"content_block_start","index":0,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":""}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"{\"locat"}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":"ion\": \"Paris\", \"days\""}}"#,
+///     r#"{"type":"content_block_delta","index":0,"delta":{"type":"input_json_delta","partial_json":": [1, 2]}"}}"#,
+///     r#"{"type":"content_block_stop","index":0}"#,
+///     r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_2","name":"get_time","input":{}}}"#,
+///     r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":""}}"#,
+///     r#"{"type":"content_block_stop","index":1}"#,
+///     r#"{"type":"message_delta","delta":{"stop_reason":"tool_use","stop_sequence":null},"usage":{"output_tokens":40}}"#,
+///     r#"{"type":"message_stop"}"#,
+/// ];
+///
+/// let mut accumulator = MessageAccumulator::new();
+/// for event in events {
+///     accumulator.push(serde_json::from_str::<StreamEvent>(event).unwrap()).unwrap();
+/// }
+/// let message = accumulator.finish().unwrap();
+///
+/// assert!(matches!(
+///     &message.content[0],
+///     ContentBlock::ToolUse { input, .. } if *input == json!({ "location": "Paris", "days": [1, 2] })
+/// ));
+/// assert!(matches!(
+///     &message.content[1],
+///     ContentBlock::ToolUse { input, .. } if *input == json!({})
+/// ));
+/// ```
+#[derive(Debug, Default)]
+pub struct MessageAccumulator {
+    /// The message from the `message_start` event
+    message: Option<MessageStartContent>,
+    /// Raw tool input fragments per content block index
+    partial_json: HashMap<usize, String>,
+    /// Stop reason from the `message_delta` event
+    stop_reason: Option<StopReason>,
+    /// Stop sequence from the `message_delta` event
+    stop_sequence: Option<String>,
+    /// Code execution container from the `message_delta` event
+    container: Option<Container>,
+}
+
+impl MessageAccumulator {
+    /// Create a new, empty MessageAccumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumes a whole event stream and returns the reassembled message
+    pub async fn accumulate<S>(stream: S) -> Result<CreateMessageResponse, MessageError>
+    where
+        S: Stream<Item = Result<StreamEvent, MessageError>>,
+    {
+        let mut stream = std::pin::pin!(stream);
+        let mut accumulator = Self::new();
+        while let Some(event) = stream.next().await {
+            accumulator.push(event?)?;
+        }
+        accumulator.finish()
+    }
+
+    /// Applies a single event to the message being reassembled
+    ///
+    /// Content blocks must start in order: a `content_block_start` event
+    /// skipping an index is rejected rather than stored at the wrong position.
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message::{MessageAccumulator, MessageError, StreamEvent};
+    ///
+    /// let mut accumulator = MessageAccumulator::new();
+    /// let start = r#"{"type":"message_start","message":{"id":"msg_1","type":"message","role":"assistant","content":[],"model":"claude-sonnet-4-0","stop_reason":null,"stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}"#;
+    /// accumulator.push(serde_json::from_str::<StreamEvent>(start).unwrap()).unwrap();
+    ///
+    /// let block = r#"{"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#;
+    /// let error = accumulator
+    ///     .push(serde_json::from_str::<StreamEvent>(block).unwrap())
+    ///     .unwrap_err();
+    /// assert!(matches!(error, MessageError::ApiError(_)));
+    /// assert!(accumulator.finish().unwrap().content.is_empty());
+    /// ```
+    pub fn push(&mut self, event: StreamEvent) -> Result<(), MessageError> {
+        match event {
+            StreamEvent::MessageStart { message } => self.message = Some(message),
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let content = &mut self.message_mut()?.content;
+                if index < content.len() {
+                    content[index] = content_block;
+                } else if index == content.len() {
+                    content.push(content_block);
+                } else {
+                    return Err(Self::missing_blocks(index, content.len()));
+                }
+            }
+            StreamEvent::ContentBlockDelta { index, delta } => match delta {
+                ContentBlockDelta::TextDelta { text: delta } => match self.block_mut(index)? {
+                    ContentBlock::Text { text, .. } => text.push_str(&delta),
+                    _ => return Err(Self::unexpected_delta("text_delta", index)),
+                },
+                ContentBlockDelta::InputJsonDelta { partial_json } => {
+                    self.partial_json
+                        .entry(index)
+                        .or_default()
+                        .push_str(&partial_json);
+                }
+                ContentBlockDelta::ThinkingDelta { thinking: delta } => {
+                    match self.block_mut(index)? {
+                        ContentBlock::Thinking { thinking, .. } => thinking.push_str(&delta),
+                        _ => return Err(Self::unexpected_delta("thinking_delta", index)),
+                    }
+                }
+                ContentBlockDelta::CitationsDelta { citation } => match self.block_mut(index)? {
+                    ContentBlock::Text { citations, .. } => {
+                        citations.get_or_insert_with(Vec::new).push(citation)
+                    }
+                    _ => return Err(Self::unexpected_delta("citations_delta", index)),
+                },
+                ContentBlockDelta::SignatureDelta { signature: delta } => {
+                    match self.block_mut(index)? {
+                        ContentBlock::Thinking { signature, .. } => signature.push_str(&delta),
+                        _ => return Err(Self::unexpected_delta("signature_delta", index)),
+                    }
+                }
+            },
+            StreamEvent::ContentBlockStop { index } => {
+                // A tool called without arguments streams empty fragments, and
+                // keeps the `{}` input of its `content_block_start` event
+                if let Some(json) = self
+                    .partial_json
+                    .remove(&index)
+                    .filter(|json| !json.trim().is_empty())
+                {
+                    let parsed = serde_json::from_str(&json).map_err(|e| {
+                        MessageError::ApiError(format!(
+                            "Failed to parse tool input of content block {}: {}. Input: {}",
+                            index, e, json
+                        ))
+                    })?;
+                    match self.block_mut(index)? {
+                        ContentBlock::ToolUse { input, .. }
+                        | ContentBlock::ServerToolUse { input, .. } => *input = parsed,
+                        _ => return Err(Self::unexpected_delta("input_json_delta", index)),
+                    }
+                }
+            }
+            StreamEvent::MessageDelta { delta, usage } => {
+                self.stop_reason = delta.stop_reason;
+                self.stop_sequence = delta.stop_sequence;
+                if delta.container.is_some() {
+                    self.container = delta.container;
+                }
+                if let Some(usage) = usage {
+                    let current = &mut self.message_mut()?.usage;
+                    current.output_tokens = usage.output_tokens;
+                    if usage.input_tokens > 0 {
+                        current.input_tokens = usage.input_tokens;
+                    }
+                    if usage.cache_creation_input_tokens.is_some() {
+                        current.cache_creation_input_tokens = usage.cache_creation_input_tokens;
+                    }
+                    if usage.cache_read_input_tokens.is_some() {
+                        current.cache_read_input_tokens = usage.cache_read_input_tokens;
+                    }
+                }
+            }
+            StreamEvent::MessageStop | StreamEvent::Ping | StreamEvent::Unknown(_) => {}
+            StreamEvent::Error { error } => {
+                return Err(MessageError::from(ApiErrorResponse::from_detail(error)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the stop reason, once the `message_delta` event has been pushed
+    pub fn stop_reason(&self) -> Option<&StopReason> {
+        self.stop_reason.as_ref()
+    }
+
+    /// Returns the stop sequence that ended generation, once the
+    /// `message_delta` event has been pushed
+    ///
+    /// Only set when the stop reason is `StopReason::StopSequence`.
+    pub fn stop_sequence(&self) -> Option<&str> {
+        self.stop_sequence.as_deref()
+    }
+
+    /// Returns the token usage reported so far
+    pub fn usage(&self) -> Option<&Usage> {
+        self.message.as_ref().map(|m| &m.usage)
+    }
+
+    /// Returns the reassembled message
+    ///
+    /// Fails if no `message_start` event has been pushed.
+    pub fn finish(self) -> Result<CreateMessageResponse, MessageError> {
+        let message = self.message.ok_or_else(Self::not_started)?;
+        Ok(CreateMessageResponse {
+            content: message.content,
+            id: message.id,
+            model: message.model,
+            role: message.role,
+            stop_reason: self.stop_reason.or(message.stop_reason),
+            stop_sequence: self.stop_sequence.or(message.stop_sequence),
+            type_: message.type_,
+            usage: message.usage,
+            container: self.container.or(message.container),
+        })
+    }
+
+    fn message_mut(&mut self) -> Result<&mut MessageStartContent, MessageError> {
+        self.message.as_mut().ok_or_else(Self::not_started)
+    }
+
+    fn block_mut(&mut self, index: usize) -> Result<&mut ContentBlock, MessageError> {
+        self.message_mut()?.content.get_mut(index).ok_or_else(|| {
+            MessageError::ApiError(format!(
+                "Received a delta for unknown content block {}",
+                index
+            ))
+        })
+    }
+
+    fn not_started() -> MessageError {
+        MessageError::ApiError("Received stream events before message_start".to_string())
+    }
+
+    fn missing_blocks(index: usize, len: usize) -> MessageError {
+        MessageError::ApiError(format!(
+            "Received content block {} before content block {}",
+            index, len
+        ))
+    }
+
+    fn unexpected_delta(delta: &str, index: usize) -> MessageError {
+        MessageError::ApiError(format!(
+            "Received {} for content block {} of a different type",
+            delta, index
+        ))
+    }
+}
@@ -2,11 +2,18 @@
 //!
 //! This module contains the types and functions for the Anthropic Message Batches API.
 //!
+use crate::client::RequestOptions;
+use crate::types::error::{ApiErrorDetail, ApiErrorResponse, ResponseTooLarge};
+use crate::types::message::{ContentBlock, Role, StopReason, SystemPrompt, Usage};
 use async_trait::async_trait;
+use futures_util::Stream;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use thiserror::Error;
 use time::OffsetDateTime;
 use time::serde::rfc3339;
+use tokio::io::AsyncWrite;
 
 /// Error types for the Message Batches API
 #[derive(Debug, Error)]
@@ -15,10 +22,34 @@ pub enum MessageBatchError {
     BatchTooLarge,
     #[error("Batch total size exceeds 256MB")]
     BatchSizeExceeded,
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
     #[error("API request failed: {0}")]
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Timed out waiting for message batch {message_batch_id} to end")]
+    WaitTimeout { message_batch_id: String },
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+    #[error("API error ({status}): {message}")]
+    ApiStatus { status: u16, message: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLarge),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl From<String> for MessageBatchError {
@@ -27,7 +58,56 @@ impl From<String> for MessageBatchError {
     }
 }
 
-#[async_trait]
+impl From<ApiErrorResponse> for MessageBatchError {
+    fn from(error: ApiErrorResponse) -> Self {
+        match error.status.as_u16() {
+            401 => MessageBatchError::Unauthorized(error.message().to_string()),
+            403 => MessageBatchError::PermissionDenied(error.message().to_string()),
+            404 => MessageBatchError::NotFound(error.message().to_string()),
+            429 => MessageBatchError::RateLimited(error.message().to_string()),
+            529 => MessageBatchError::Overloaded(error.message().to_string()),
+            status => MessageBatchError::ApiStatus {
+                status,
+                message: error.message().to_string(),
+            },
+        }
+    }
+}
+
+impl MessageBatchError {
+    /// Returns the HTTP status code of the failed response, if there was one
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            MessageBatchError::Unauthorized(_) => Some(401),
+            MessageBatchError::PermissionDenied(_) => Some(403),
+            MessageBatchError::NotFound(_) => Some(404),
+            MessageBatchError::RateLimited(_) => Some(429),
+            MessageBatchError::Overloaded(_) => Some(529),
+            MessageBatchError::ApiStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the failed request is worth retrying
+    ///
+    /// Rate limits, overloaded and other server errors, and requests that
+    /// failed to send are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            MessageBatchError::RequestFailed(_)
+            | MessageBatchError::RateLimited(_)
+            | MessageBatchError::Overloaded(_) => true,
+            MessageBatchError::ApiStatus { status, .. } => {
+                StatusCode::from_u16(*status).is_ok_and(ApiErrorResponse::is_retryable_status)
+            }
+            MessageBatchError::Http(error) => ApiErrorResponse::is_retryable_error(error),
+            _ => false,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait MessageBatchClient {
     /// Create a new message batch
     async fn create_message_batch<'a>(
@@ -41,6 +121,17 @@ pub trait MessageBatchClient {
         params: Option<&'a ListMessageBatchesParams>,
     ) -> Result<ListMessageBatchesResponse, MessageBatchError>;
 
+    /// Stream all message batches, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_message_batches_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+    ) -> impl Stream<Item = Result<MessageBatch, MessageBatchError>> + 'a
+    where
+        Self: Sized;
+
     /// Retrieve a message batch
     async fn retrieve_message_batch<'a>(
         &'a self,
@@ -66,19 +157,249 @@ pub trait MessageBatchClient {
         &'a self,
         params: &'a DeleteMessageBatchParams,
     ) -> Result<DeleteResponse, MessageBatchError>;
+
+    /// Same as `create_message_batch`, with per-request options
+    ///
+    /// Defaults to `create_message_batch`, ignoring `options`.
+    async fn create_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a CreateMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<MessageBatch, MessageBatchError> {
+        let _ = options;
+        self.create_message_batch(params).await
+    }
+
+    /// Same as `list_message_batches`, with per-request options
+    ///
+    /// Defaults to `list_message_batches`, ignoring `options`.
+    async fn list_message_batches_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListMessageBatchesParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListMessageBatchesResponse, MessageBatchError> {
+        let _ = options;
+        self.list_message_batches(params).await
+    }
+
+    /// Same as `retrieve_message_batch`, with per-request options
+    ///
+    /// Defaults to `retrieve_message_batch`, ignoring `options`.
+    async fn retrieve_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<RetrieveMessageBatchResponse, MessageBatchError> {
+        let _ = options;
+        self.retrieve_message_batch(params).await
+    }
+
+    /// Same as `retrieve_message_batch_results`, with per-request options
+    ///
+    /// Defaults to `retrieve_message_batch_results`, ignoring `options`.
+    async fn retrieve_message_batch_results_with_options<'a>(
+        &'a self,
+        params: &'a RetrieveMessageBatchResultsParams,
+        options: &'a RequestOptions,
+    ) -> Result<RetrieveMessageBatchResultsResponse, MessageBatchError> {
+        let _ = options;
+        self.retrieve_message_batch_results(params).await
+    }
+
+    /// Same as `cancel_message_batch`, with per-request options
+    ///
+    /// Defaults to `cancel_message_batch`, ignoring `options`.
+    async fn cancel_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a CancelMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<CancelResponse, MessageBatchError> {
+        let _ = options;
+        self.cancel_message_batch(params).await
+    }
+
+    /// Stream the results of a message batch
+    ///
+    /// Results are read line by line from the batch's `results_url` instead of
+    /// being collected in memory. A line that cannot be parsed is yielded as an
+    /// error and the stream continues with the next line.
+    ///
+    /// Not available on `dyn MessageBatchClient`, since the stream type is
+    /// opaque.
+    async fn stream_message_batch_results<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+    ) -> Result<
+        impl Stream<Item = Result<MessageBatchResult, MessageBatchError>> + 'a,
+        MessageBatchError,
+    >
+    where
+        Self: Sized;
+
+    /// Same as `stream_message_batch_results`, with per-request options
+    ///
+    /// Defaults to `stream_message_batch_results`, ignoring `options`.
+    async fn stream_message_batch_results_with_options<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<
+        impl Stream<Item = Result<MessageBatchResult, MessageBatchError>> + 'a,
+        MessageBatchError,
+    >
+    where
+        Self: Sized,
+    {
+        let _ = options;
+        self.stream_message_batch_results(message_batch_id).await
+    }
+
+    /// Write the raw results of a message batch to `writer`
+    ///
+    /// The JSONL body of the batch's `results_url` is copied to the writer as
+    /// it arrives, without being parsed or held in memory. Returns the number
+    /// of bytes written.
+    async fn download_batch_results_to<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        writer: &'a mut (dyn AsyncWrite + Unpin + Send),
+    ) -> Result<u64, MessageBatchError>;
+
+    /// Same as `download_batch_results_to`, with per-request options
+    ///
+    /// Defaults to `download_batch_results_to`, ignoring `options`.
+    async fn download_batch_results_to_with_options<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        writer: &'a mut (dyn AsyncWrite + Unpin + Send),
+        options: &'a RequestOptions,
+    ) -> Result<u64, MessageBatchError> {
+        let _ = options;
+        self.download_batch_results_to(message_batch_id, writer)
+            .await
+    }
+
+    /// Same as `delete_message_batch`, with per-request options
+    ///
+    /// Defaults to `delete_message_batch`, ignoring `options`.
+    async fn delete_message_batch_with_options<'a>(
+        &'a self,
+        params: &'a DeleteMessageBatchParams,
+        options: &'a RequestOptions,
+    ) -> Result<DeleteResponse, MessageBatchError> {
+        let _ = options;
+        self.delete_message_batch(params).await
+    }
+
+    /// Create a message batch and wait until its results are available
+    ///
+    /// Polls the batch every `poll_interval` until it has ended, calling
+    /// `on_progress` with the batch after each poll, then downloads all of its
+    /// results. Fails with `MessageBatchError::WaitTimeout` if the batch has not
+    /// ended within `timeout`; the batch keeps processing in that case.
+    async fn create_and_wait<'a>(
+        &'a self,
+        params: &'a CreateMessageBatchParams,
+        poll_interval: Duration,
+        timeout: Duration,
+        on_progress: Option<&'a (dyn for<'b> Fn(&'b MessageBatch) + Send + Sync)>,
+    ) -> Result<Vec<MessageBatchResult>, MessageBatchError>;
+
+    /// Wait until a message batch has ended
+    ///
+    /// Polls the batch every `config.interval` until its processing status is
+    /// `Ended`, which is also the case once a cancellation completes, and
+    /// returns the final batch. Fails with `MessageBatchError::WaitTimeout` if
+    /// the batch has not ended within `config.timeout`.
+    async fn wait_for_batch<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        config: PollConfig,
+    ) -> Result<MessageBatch, MessageBatchError>;
+
+    /// Same as `wait_for_batch`, calling `on_progress` with the request
+    /// counts of the batch after each successful poll
+    async fn wait_for_batch_with_progress<'a>(
+        &'a self,
+        message_batch_id: &'a str,
+        config: PollConfig,
+        on_progress: &'a mut (dyn for<'b> FnMut(&'b RequestCounts) + Send),
+    ) -> Result<MessageBatch, MessageBatchError>;
+}
+
+/// How to poll a message batch until it ends
+#[derive(Debug, Clone, Copy)]
+pub struct PollConfig {
+    /// Delay between two status checks
+    pub interval: Duration,
+    /// How long to wait for the batch to end
+    pub timeout: Duration,
+    /// How many consecutive status checks may fail with a retryable error,
+    /// such as a rate limit, before the wait is given up
+    pub max_poll_failures: u32,
+}
+
+impl PollConfig {
+    /// Default delay between two status checks
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// Default wait, the time after which an unfinished batch expires
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Default number of consecutive failed status checks that are tolerated
+    pub const DEFAULT_MAX_POLL_FAILURES: u32 = 2;
+
+    /// Create a new PollConfig with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the delay between two status checks
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how long to wait for the batch to end
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how many consecutive status checks may fail with a retryable error
+    pub fn with_max_poll_failures(mut self, max_poll_failures: u32) -> Self {
+        self.max_poll_failures = max_poll_failures;
+        self
+    }
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: Self::DEFAULT_INTERVAL,
+            timeout: Self::DEFAULT_TIMEOUT,
+            max_poll_failures: Self::DEFAULT_MAX_POLL_FAILURES,
+        }
+    }
 }
 
 /// Processing status of a Message Batch
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Statuses added to the API after this version of the crate deserialize as
+/// `Unknown` instead of failing the whole response.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ProcessingStatus {
     InProgress,
     Canceling,
     Ended,
+    /// A status not known to this version of the crate
+    #[serde(other)]
+    Unknown,
 }
 
 /// Request counts for different statuses
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub struct RequestCounts {
     /// Number of requests currently processing
     pub processing: u32,
@@ -92,6 +413,29 @@ pub struct RequestCounts {
     pub expired: u32,
 }
 
+impl RequestCounts {
+    /// Returns the number of requests in the batch, whatever their status
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message_batches::RequestCounts;
+    ///
+    /// let counts = RequestCounts {
+    ///     processing: 3,
+    ///     succeeded: 5,
+    ///     errored: 1,
+    ///     canceled: 0,
+    ///     expired: 1,
+    /// };
+    /// assert_eq!(counts.total(), 10);
+    /// println!("succeeded {} of {}", counts.succeeded, counts.total());
+    /// ```
+    pub fn total(&self) -> u32 {
+        self.processing + self.succeeded + self.errored + self.canceled + self.expired
+    }
+}
+
 /// Response structure for Message Batch creation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageBatch {
@@ -124,14 +468,14 @@ pub struct MessageBatch {
 }
 
 /// Parameters for creating a message batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct CreateMessageBatchParams {
     /// List of message creation requests
     pub requests: Vec<MessageRequest>,
 }
 
 /// Individual message request within a batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct MessageRequest {
     /// Custom identifier for tracking this request
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -141,7 +485,7 @@ pub struct MessageRequest {
 }
 
 /// Parameters for an individual message request
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct MessageRequestParams {
     /// Model to use for this message
     pub model: String,
@@ -149,28 +493,137 @@ pub struct MessageRequestParams {
     pub max_tokens: u32,
     /// System prompt for the message
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub system: Option<String>,
+    pub system: Option<SystemPrompt>,
     /// User message content
     pub messages: Vec<Message>,
 }
 
 /// Message content
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct Message {
     /// Role of the message sender
-    pub role: String,
+    pub role: Role,
     /// Content of the message
     pub content: String,
 }
 
 impl CreateMessageBatchParams {
+    /// Maximum number of requests in a batch
+    pub const MAX_REQUESTS: usize = 100_000;
+
+    /// Maximum size of a batch, in bytes of serialized JSON
+    pub const MAX_SIZE_BYTES: usize = 256 * 1024 * 1024;
+
     /// Create a new CreateMessageBatchParams with the given requests
+    ///
+    /// The number of requests is not checked here: `create_message_batch`
+    /// rejects a batch larger than `MAX_REQUESTS` before sending it. Use
+    /// `try_new` to check it up front.
     pub fn new(requests: Vec<MessageRequest>) -> Self {
-        if requests.len() > 100_000 {
-            panic!("Batch size exceeds maximum limit of 100,000 requests");
-        }
         Self { requests }
     }
+
+    /// Create a new CreateMessageBatchParams, checking the number of requests
+    /// and the size of the batch
+    ///
+    /// # Errors
+    ///
+    /// Returns `MessageBatchError::BatchTooLarge` if there are more than
+    /// `MAX_REQUESTS` requests, `MessageBatchError::BatchSizeExceeded` if
+    /// the batch is larger than `MAX_SIZE_BYTES`, or
+    /// `MessageBatchError::InvalidParameter` if a message has an unknown role.
+    pub fn try_new(requests: Vec<MessageRequest>) -> Result<Self, MessageBatchError> {
+        let params = Self { requests };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Checks the parameters for mistakes the API would reject
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, Message, MessageBatchError, MessageRequest,
+    ///     MessageRequestParams,
+    /// };
+    ///
+    /// let batch = |role: &str| {
+    ///     let messages = vec![Message::new(role, "Hello!")];
+    ///     let params = MessageRequestParams::new("claude-3-haiku", messages, 100);
+    ///     CreateMessageBatchParams::new(vec![MessageRequest::new(params)])
+    /// };
+    ///
+    /// assert!(batch("User").validate().is_ok());
+    /// assert!(matches!(
+    ///     batch("system").validate(),
+    ///     Err(MessageBatchError::InvalidParameter(_))
+    /// ));
+    /// ```
+    pub fn validate(&self) -> Result<(), MessageBatchError> {
+        if self.requests.len() > Self::MAX_REQUESTS {
+            return Err(MessageBatchError::BatchTooLarge);
+        }
+        if self.estimated_size_bytes() > Self::MAX_SIZE_BYTES {
+            return Err(MessageBatchError::BatchSizeExceeded);
+        }
+        for (index, request) in self.requests.iter().enumerate() {
+            let messages = &request.params.messages;
+            if let Some(position) = messages.iter().position(|m| m.role == Role::Other) {
+                return Err(MessageBatchError::InvalidParameter(format!(
+                    "requests[{}].messages[{}]: role must be \"user\" or \"assistant\"",
+                    index, position
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the size of the request body for these parameters, in bytes
+    ///
+    /// The batch is serialized to count its bytes, without being buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anthropic_ai_sdk::types::message_batches::{
+    ///     CreateMessageBatchParams, Message, MessageRequest, MessageRequestParams,
+    /// };
+    ///
+    /// let messages = vec![Message::new("user", "Hello!")];
+    /// let request = MessageRequest::new(MessageRequestParams::new("claude-3-haiku", messages, 100))
+    ///     .with_custom_id("req1");
+    /// let params = CreateMessageBatchParams::new(vec![request]);
+    ///
+    /// let size = params.estimated_size_bytes();
+    /// assert_eq!(size, serde_json::to_vec(&params).unwrap().len());
+    /// assert!(size <= CreateMessageBatchParams::MAX_SIZE_BYTES);
+    /// ```
+    pub fn estimated_size_bytes(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        serde_json::to_writer(&mut counter, self)
+            .expect("CreateMessageBatchParams always serializes to JSON");
+        counter.0
+    }
+
+    /// Returns the JSON body that is sent for these parameters
+    pub fn to_wire_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("CreateMessageBatchParams always serializes to JSON")
+    }
+}
+
+/// Writer counting the bytes written to it
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 impl MessageRequest {
@@ -202,16 +655,27 @@ impl MessageRequestParams {
 
     /// Set a system prompt for this request
     pub fn with_system(mut self, system: impl Into<String>) -> Self {
-        self.system = Some(system.into());
+        self.system = Some(SystemPrompt::Text(system.into()));
+        self
+    }
+
+    /// Set the system prompt as text blocks, for example to cache it across
+    /// the requests of the batch with [`ContentBlock::cached`]
+    pub fn with_system_blocks(mut self, blocks: Vec<ContentBlock>) -> Self {
+        self.system = Some(SystemPrompt::Blocks(blocks));
         self
     }
 }
 
 impl Message {
     /// Create a new Message
-    pub fn new(role: impl Into<String>, content: impl Into<String>) -> Self {
+    ///
+    /// The role can be given as a `Role` or as a string such as `"user"`,
+    /// in any case. A string naming no known role gives `Role::Other`, which
+    /// `CreateMessageBatchParams::validate` rejects.
+    pub fn new(role: impl AsRef<str>, content: impl Into<String>) -> Self {
         Self {
-            role: role.into(),
+            role: Role::try_from(role.as_ref()).unwrap_or(Role::Other),
             content: content.into(),
         }
     }
@@ -231,7 +695,7 @@ pub struct ListMessageBatchesResponse {
 }
 
 /// Parameters for listing message batches
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct ListMessageBatchesParams {
     /// Cursor for pagination (before)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -270,7 +734,7 @@ impl ListMessageBatchesParams {
 }
 
 /// Parameters for retrieving a message batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct RetrieveMessageBatchParams {
     /// ID of the message batch to retrieve
     pub message_batch_id: String,
@@ -285,7 +749,7 @@ impl RetrieveMessageBatchParams {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct RetrieveMessageBatchResultsParams {
     /// ID of the message batch to retrieve
     pub message_batch_id: String,
@@ -311,15 +775,116 @@ pub struct MessageBatchResult {
     pub result: BatchRequestResult,
 }
 
+/// Outcome of one request of a message batch
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message_batches::{BatchRequestResult, MessageBatchResult};
+///
+/// let result: MessageBatchResult = serde_json::from_str(
+///     r#"{
+///         "custom_id": "request-2",
+///         "result": {
+///             "type": "errored",
+///             "error": {
+///                 "type": "error",
+///                 "error": { "type": "invalid_request_error", "message": "max_tokens: Field required" }
+///             }
+///         }
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// match result.result {
+///     BatchRequestResult::Errored { error } => assert_eq!(error.type_, "invalid_request_error"),
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
 #[derive(Debug, Serialize, Deserialize)]
-pub struct BatchRequestResult {
-    /// Type of result (e.g., "succeeded")
-    #[serde(rename = "type")]
-    pub type_: String,
-    /// The resulting message if successful
-    pub message: MessageResponse,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchRequestResult {
+    /// The request succeeded
+    Succeeded {
+        /// The resulting message
+        message: MessageResponse,
+    },
+    /// The request failed
+    Errored {
+        /// Error of the request
+        #[serde(deserialize_with = "deserialize_result_error")]
+        error: ApiErrorDetail,
+    },
+    /// The batch was canceled before the request was processed
+    Canceled,
+    /// The batch expired before the request was processed
+    Expired,
+}
+
+impl BatchRequestResult {
+    /// Returns the resulting message, if the request succeeded
+    pub fn message(&self) -> Option<&MessageResponse> {
+        match self {
+            Self::Succeeded { message } => Some(message),
+            _ => None,
+        }
+    }
 }
 
+/// Reads the error of an errored result, whether it is wrapped in an error
+/// response envelope or not
+fn deserialize_result_error<'de, D>(deserializer: D) -> Result<ApiErrorDetail, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ResultError {
+        Envelope { error: ApiErrorDetail },
+        Detail(ApiErrorDetail),
+    }
+
+    Ok(match ResultError::deserialize(deserializer)? {
+        ResultError::Envelope { error } | ResultError::Detail(error) => error,
+    })
+}
+
+/// Message generated for one request of the batch
+///
+/// The content is parsed into the same [`ContentBlock`]s as a
+/// `CreateMessageResponse`, so block types this SDK does not know yet are
+/// kept as `ContentBlock::Unknown`.
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::message::ContentBlock;
+/// use anthropic_ai_sdk::types::message_batches::MessageResponse;
+/// use serde_json::json;
+///
+/// let message: MessageResponse = serde_json::from_value(json!({
+///     "id": "msg_123",
+///     "type": "message",
+///     "role": "assistant",
+///     "model": "claude-3-5-sonnet-20240620",
+///     "content": [
+///         { "type": "text", "text": "Let me check." },
+///         { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "Paris" } },
+///         { "type": "hologram", "frames": 3 }
+///     ],
+///     "stop_reason": "tool_use",
+///     "stop_sequence": null,
+///     "usage": { "input_tokens": 10, "output_tokens": 20 }
+/// }))?;
+///
+/// assert!(matches!(&message.content[0], ContentBlock::Text { text, .. } if text == "Let me check."));
+/// assert!(matches!(&message.content[1], ContentBlock::ToolUse { name, .. } if name == "get_weather"));
+/// assert_eq!(
+///     message.content[2],
+///     ContentBlock::Unknown(json!({ "type": "hologram", "frames": 3 }))
+/// );
+/// # Ok::<(), serde_json::Error>(())
+/// ```
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MessageResponse {
     /// Unique identifier for the message
@@ -327,42 +892,29 @@ pub struct MessageResponse {
     /// Type of the response (always "message")
     #[serde(rename = "type")]
     pub type_: String,
-    /// Role of the message (e.g., "assistant")
-    pub role: String,
+    /// Role of the message (always `Role::Assistant`)
+    pub role: Role,
     /// Model used for generation
     pub model: String,
-    /// Content of the message
-    pub content: Vec<MessageContent>,
+    /// Content blocks of the message
+    pub content: Vec<ContentBlock>,
     /// Reason for stopping generation
-    pub stop_reason: String,
+    pub stop_reason: StopReason,
     /// Sequence that caused the stop
     pub stop_sequence: Option<String>,
     /// Token usage statistics
-    pub usage: TokenUsage,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MessageContent {
-    /// Type of content (e.g., "text")
-    #[serde(rename = "type")]
-    pub type_: String,
-    /// The actual text content
-    pub text: String,
+    pub usage: Usage,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TokenUsage {
-    /// Number of tokens in the input
-    pub input_tokens: u32,
-    /// Number of tokens in the output
-    pub output_tokens: u32,
-}
+/// Token usage statistics of a batch result
+#[deprecated(note = "use `types::message::Usage`, which also reports cache tokens")]
+pub type TokenUsage = Usage;
 
 /// Response type for retrieving message batch results
 /// This will be a stream of MessageBatchResult objects, one per line
 pub type RetrieveMessageBatchResultsResponse = Vec<MessageBatchResult>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct CancelMessageBatchParams {
     pub message_batch_id: String,
 }
@@ -376,23 +928,13 @@ impl CancelMessageBatchParams {
 }
 
 /// Response type for cancelling a message batch
-#[derive(Debug, Deserialize)]
-pub struct CancelResponse {
-    pub id: String,
-    #[serde(rename = "type")]
-    pub obj_type: String,
-    pub processing_status: String,
-    pub request_counts: serde_json::Value,
-    pub ended_at: Option<String>,
-    pub created_at: String,
-    pub expires_at: String,
-    pub archived_at: Option<String>,
-    pub cancel_initiated_at: Option<String>,
-    pub results_url: Option<String>,
-}
+///
+/// The API returns the batch itself, with `processing_status` set to
+/// `canceling` until the requests in progress have finished.
+pub type CancelResponse = MessageBatch;
 
 /// Parameters for deleting a message batch
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
 pub struct DeleteMessageBatchParams {
     /// ID of the message batch to delete
     pub message_batch_id: String,
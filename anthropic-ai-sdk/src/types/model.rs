@@ -2,11 +2,15 @@
 //!
 //! This module contains the types and functions for the Anthropic Models API.
 //!
+use crate::client::RequestOptions;
+use crate::types::error::{ApiErrorResponse, ResponseTooLarge};
 use async_trait::async_trait;
+use futures_util::Stream;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use time::serde::rfc3339;
 use time::OffsetDateTime;
+use time::serde::rfc3339;
 
 /// Error types for the Models API
 #[derive(Debug, Error)]
@@ -19,6 +23,26 @@ pub enum ModelError {
     RequestFailed(String),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    #[error("Overloaded: {0}")]
+    Overloaded(String),
+    #[error("API error ({status}): {message}")]
+    ApiStatus { status: u16, message: String },
+    #[error("Unexpected response: expected a {expected} object, got type {got:?}")]
+    UnexpectedResponse { expected: &'static str, got: String },
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    ResponseTooLarge(#[from] ResponseTooLarge),
 }
 
 impl From<String> for ModelError {
@@ -27,7 +51,57 @@ impl From<String> for ModelError {
     }
 }
 
-#[async_trait]
+impl From<ApiErrorResponse> for ModelError {
+    fn from(error: ApiErrorResponse) -> Self {
+        match error.status.as_u16() {
+            401 => ModelError::Unauthorized(error.message().to_string()),
+            403 => ModelError::PermissionDenied(error.message().to_string()),
+            404 => ModelError::NotFound(error.message().to_string()),
+            429 => ModelError::RateLimited(error.message().to_string()),
+            529 => ModelError::Overloaded(error.message().to_string()),
+            status => ModelError::ApiStatus {
+                status,
+                message: error.message().to_string(),
+            },
+        }
+    }
+}
+
+impl ModelError {
+    /// Returns the HTTP status code of the failed response, if there was one
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            ModelError::Unauthorized(_) => Some(401),
+            ModelError::PermissionDenied(_) => Some(403),
+            ModelError::NotFound(_) => Some(404),
+            ModelError::RateLimited(_) => Some(429),
+            ModelError::Overloaded(_) => Some(529),
+            ModelError::ApiStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the failed request is worth retrying
+    ///
+    /// Rate limits, overloaded and other server errors, and requests that
+    /// failed to send are retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ModelError::RequestFailed(_)
+            | ModelError::RateLimited(_)
+            | ModelError::Overloaded(_) => true,
+            ModelError::ApiStatus { status, .. } => {
+                StatusCode::from_u16(*status).is_ok_and(ApiErrorResponse::is_retryable_status)
+            }
+            ModelError::Http(error) => ApiErrorResponse::is_retryable_error(error),
+            _ => false,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 pub trait ModelClient {
     async fn list_models<'a>(
         &'a self,
@@ -35,6 +109,45 @@ pub trait ModelClient {
     ) -> Result<ListModelsResponse, ModelError>;
 
     async fn get_model<'a>(&'a self, model_id: &'a str) -> Result<Model, ModelError>;
+
+    /// Resolve a model alias, such as `claude-3-5-sonnet-latest`, to the
+    /// model it currently points to
+    async fn resolve_model_alias<'a>(&'a self, alias: &'a str) -> Result<Model, ModelError>;
+
+    /// Stream all models, fetching the following pages as needed
+    ///
+    /// Starts from the cursor in `params`, if any, and uses its `limit` as
+    /// the page size. Pages are followed backwards when `before_id` is set.
+    fn list_models_paginated<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+    ) -> impl Stream<Item = Result<Model, ModelError>> + 'a
+    where
+        Self: Sized;
+
+    /// Same as `list_models`, with per-request options
+    ///
+    /// Defaults to `list_models`, ignoring `options`.
+    async fn list_models_with_options<'a>(
+        &'a self,
+        params: Option<&'a ListModelsParams>,
+        options: &'a RequestOptions,
+    ) -> Result<ListModelsResponse, ModelError> {
+        let _ = options;
+        self.list_models(params).await
+    }
+
+    /// Same as `get_model`, with per-request options
+    ///
+    /// Defaults to `get_model`, ignoring `options`.
+    async fn get_model_with_options<'a>(
+        &'a self,
+        model_id: &'a str,
+        options: &'a RequestOptions,
+    ) -> Result<Model, ModelError> {
+        let _ = options;
+        self.get_model(model_id).await
+    }
 }
 
 /// Response structure for the List Models API endpoint
@@ -51,6 +164,25 @@ pub struct ListModelsResponse {
 }
 
 /// Represents an Anthropic model
+///
+/// # Examples
+///
+/// ```
+/// use anthropic_ai_sdk::types::model::Model;
+///
+/// let model: Model = serde_json::from_str(
+///     r#"{
+///         "type": "model",
+///         "id": "claude-3-5-sonnet-20241022",
+///         "display_name": "Claude 3.5 Sonnet (New)",
+///         "created_at": "2024-10-22T00:00:00Z",
+///         "max_input_tokens": 200000
+///     }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(model.extra["max_input_tokens"], 200000);
+/// ```
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Model {
     /// Type of the resource (always "model")
@@ -68,10 +200,14 @@ pub struct Model {
     #[serde(rename = "created_at")]
     #[serde(with = "rfc3339")]
     pub created_at: OffsetDateTime,
+
+    /// Fields returned by the API that this version of the SDK does not model
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Parameters for listing models
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Default, Clone, PartialEq, Eq)]
 pub struct ListModelsParams {
     /// Cursor for pagination (before)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,1638 @@
+//! HTTP layer tests
+//!
+//! Each test points a client at a local `wiremock` server and checks the
+//! request it sends: method, path, authentication headers and body. To cover
+//! a new endpoint, mount a mock matching the expected request with
+//! `expect(1)`; the server verifies the expectations when it is dropped.
+
+use anthropic_ai_sdk::client::{AnthropicClient, RequestOptions};
+use anthropic_ai_sdk::messages::parse_sse_stream;
+use anthropic_ai_sdk::types::admin::api_keys::{AdminClient, AdminError};
+use anthropic_ai_sdk::types::admin::invites::InviteClient;
+use anthropic_ai_sdk::types::admin::reporting::{
+    CostReportClient, UsageGroupBy, UsageReportClient, UsageReportParams,
+};
+use anthropic_ai_sdk::types::admin::users::UserClient;
+use anthropic_ai_sdk::types::admin::workspace_members::WorkspaceMemberClient;
+use anthropic_ai_sdk::types::admin::workspaces::WorkspaceClient;
+use anthropic_ai_sdk::types::error::ResponseTooLarge;
+use anthropic_ai_sdk::types::files::{FILES_API_BETA, FileError, FilesClient};
+use anthropic_ai_sdk::types::message::{
+    ContentBlockDelta, CountMessageTokensParams, CreateMessageParams, Message, MessageClient,
+    MessageError, RequiredMessageParams, StreamEvent,
+};
+use anthropic_ai_sdk::types::message_batches::{
+    CancelMessageBatchParams, DeleteMessageBatchParams, MessageBatchClient, MessageBatchError,
+    PollConfig, ProcessingStatus, RetrieveMessageBatchParams,
+};
+use anthropic_ai_sdk::types::model::{ListModelsParams, ModelClient, ModelError};
+use futures_util::StreamExt;
+use reqwest::header::{HeaderName, HeaderValue};
+use serde_json::{Value, json};
+use std::error::Error as StdError;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use wiremock::matchers::{
+    body_json, body_partial_json, body_string_contains, header, method, path, query_param,
+    query_param_is_missing,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const API_KEY: &str = "test-api-key";
+const API_VERSION: &str = "2023-06-01";
+
+/// Builds a client sending its requests to the mock server
+fn client<E>(server: &MockServer) -> AnthropicClient
+where
+    E: StdError + From<String>,
+{
+    AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .build::<E>()
+        .unwrap()
+}
+
+/// Matches the headers every authenticated request carries
+fn authenticated(mock: wiremock::MockBuilder) -> wiremock::MockBuilder {
+    mock.and(header("x-api-key", API_KEY))
+        .and(header("anthropic-version", API_VERSION))
+}
+
+fn message_batch(id: &str, processing_status: &str) -> Value {
+    json!({
+        "id": id,
+        "type": "message_batch",
+        "created_at": "2024-09-24T18:37:24.100435Z",
+        "expires_at": "2024-09-25T18:37:24.100435Z",
+        "archived_at": null,
+        "cancel_initiated_at": null,
+        "ended_at": null,
+        "processing_status": processing_status,
+        "request_counts": {
+            "processing": 1,
+            "succeeded": 0,
+            "errored": 0,
+            "canceled": 0,
+            "expired": 0
+        },
+        "results_url": null
+    })
+}
+
+#[tokio::test]
+async fn create_message_sends_params() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/messages")))
+        .and(header("content-type", "application/json"))
+        .and(body_json(json!({
+            "model": "claude-3-5-sonnet-20240620",
+            "messages": [{"role": "user", "content": "Hello, Claude"}],
+            "max_tokens": 1024
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_01",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-3-5-sonnet-20240620",
+            "content": [{"type": "text", "text": "Hello!"}],
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 2}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let response = client::<MessageError>(&server)
+        .create_message(Some(&params))
+        .await
+        .unwrap();
+
+    assert_eq!(response.id, "msg_01");
+    assert_eq!(response.text(), "Hello!");
+    assert_eq!(response.usage.output_tokens, 2);
+}
+
+#[tokio::test]
+async fn create_message_maps_error_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "authentication_error", "message": "invalid x-api-key"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let error = client::<MessageError>(&server)
+        .create_message(Some(&params))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MessageError::Unauthorized(_)), "{error:?}");
+    assert_eq!(error.status_code(), Some(401));
+}
+
+#[tokio::test]
+async fn create_message_reports_the_deprecated_model() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "type": "error",
+            "error": {
+                "type": "not_found_error",
+                "message": "model: claude-2.0 has been retired and is no longer available"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-2.0".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let error = client::<MessageError>(&server)
+        .create_message(Some(&params))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, MessageError::ModelDeprecated { model, .. } if model == "claude-2.0"),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn other_errors_mentioning_a_retired_model_keep_their_kind() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {
+                "type": "overloaded_error",
+                "message": "Overloaded while traffic moves off a retired model"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let error = client::<MessageError>(&server)
+        .create_message(Some(&params))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, MessageError::Overloaded(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn identical_token_counts_are_served_from_the_cache() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/messages/count_tokens")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"input_tokens": 14})))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_count_tokens_cache(16, Duration::from_secs(60))
+        .build::<MessageError>()
+        .unwrap();
+    let params = CountMessageTokensParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        let count = client.count_tokens(Some(&params)).await.unwrap();
+        assert_eq!(count.input_tokens, 14);
+    }
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    // Options that change what is sent bypass the cache
+    let options = RequestOptions::new().with_beta("token-counting-2024-11-01");
+    let count = client
+        .count_tokens_with_options(Some(&params), &options)
+        .await
+        .unwrap();
+    assert_eq!(count.input_tokens, 14);
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].headers.get("anthropic-beta").unwrap(),
+        "token-counting-2024-11-01"
+    );
+}
+
+#[tokio::test]
+async fn list_models_sends_query() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models")))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "type": "model",
+                "id": "claude-3-5-sonnet-20240620",
+                "display_name": "Claude 3.5 Sonnet",
+                "created_at": "2024-06-20T00:00:00Z"
+            }],
+            "first_id": "claude-3-5-sonnet-20240620",
+            "has_more": false,
+            "last_id": "claude-3-5-sonnet-20240620"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = ListModelsParams::new().limit(2);
+    let response = client::<ModelError>(&server)
+        .list_models(Some(&params))
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.len(), 1);
+    assert_eq!(response.data[0].display_name, "Claude 3.5 Sonnet");
+    assert!(!response.has_more);
+}
+
+#[tokio::test]
+async fn client_traits_can_be_used_as_trait_objects() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let models: Box<dyn ModelClient + Send + Sync> = Box::new(client::<ModelError>(&server));
+    let model = models
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+    assert_eq!(model.id, "claude-3-5-sonnet-20240620");
+
+    let admin = client::<ModelError>(&server);
+    let _: &dyn MessageClient = &admin;
+    let _: &dyn MessageBatchClient = &admin;
+    let _: &dyn AdminClient = &admin;
+    let _: &dyn FilesClient = &admin;
+    let _: &dyn UsageReportClient = &admin;
+    let _: &dyn CostReportClient = &admin;
+    let _: &dyn WorkspaceClient = &admin;
+    let _: &dyn WorkspaceMemberClient = &admin;
+    let _: &dyn UserClient = &admin;
+    let _: &dyn InviteClient = &admin;
+}
+
+#[tokio::test]
+async fn get_model_rejects_other_objects() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [],
+            "has_more": false,
+            "first_id": null,
+            "last_id": null
+        })))
+        .mount(&server)
+        .await;
+
+    let error = client::<ModelError>(&server)
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            ModelError::UnexpectedResponse { expected: "model", got } if got == "none"
+        ),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn resolve_model_alias_returns_the_dated_model() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-latest")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "claude-3-5-sonnet-20241022",
+            "display_name": "Claude 3.5 Sonnet (New)",
+            "created_at": "2024-10-22T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let model = client::<ModelError>(&server)
+        .resolve_model_alias("claude-3-5-sonnet-latest")
+        .await
+        .unwrap();
+
+    assert_eq!(model.id, "claude-3-5-sonnet-20241022");
+}
+
+#[tokio::test]
+async fn compressed_responses_are_decoded() {
+    use std::io::Write;
+
+    let server = MockServer::start().await;
+    let model = json!({
+        "type": "model",
+        "id": "claude-3-5-sonnet-20240620",
+        "display_name": "Claude 3.5 Sonnet",
+        "created_at": "2024-06-20T00:00:00Z"
+    });
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(model.to_string().as_bytes()).unwrap();
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_raw(encoder.finish().unwrap(), "application/json"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let model = client::<ModelError>(&server)
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    assert_eq!(model.display_name, "Claude 3.5 Sonnet");
+    let requests = server.received_requests().await.unwrap();
+    let accept_encoding = requests[0].headers["accept-encoding"].to_str().unwrap();
+    assert!(accept_encoding.contains("gzip"), "{accept_encoding}");
+}
+
+#[tokio::test]
+async fn responses_larger_than_the_limit_are_rejected() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "claude-3-5-sonnet-20240620",
+            "display_name": "x".repeat(1024),
+            "created_at": "2024-06-20T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_max_response_bytes(512)
+        .build::<ModelError>()
+        .unwrap();
+    let error = client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            error,
+            ModelError::ResponseTooLarge(ResponseTooLarge { limit: 512 })
+        ),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn retrieve_message_batch_uses_batch_id_in_path() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/messages/batches/msgbatch_01")))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(message_batch("msgbatch_01", "in_progress")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = RetrieveMessageBatchParams::new("msgbatch_01");
+    let batch = client::<MessageBatchError>(&server)
+        .retrieve_message_batch(&params)
+        .await
+        .unwrap();
+
+    assert_eq!(batch.id, "msgbatch_01");
+    assert_eq!(batch.processing_status, ProcessingStatus::InProgress);
+}
+
+#[tokio::test]
+async fn cancel_message_batch_posts_to_cancel() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/messages/batches/msgbatch_01/cancel")))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(message_batch("msgbatch_01", "canceling")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = CancelMessageBatchParams::new("msgbatch_01");
+    let batch = client::<MessageBatchError>(&server)
+        .cancel_message_batch(&params)
+        .await
+        .unwrap();
+
+    assert_eq!(batch.id, "msgbatch_01");
+    assert_eq!(batch.processing_status, ProcessingStatus::Canceling);
+}
+
+#[tokio::test]
+async fn delete_message_batch_sends_delete() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("DELETE")).and(path("/messages/batches/msgbatch_01")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msgbatch_01",
+            "type": "message_batch_deleted"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = DeleteMessageBatchParams::new("msgbatch_01");
+    let deleted = client::<MessageBatchError>(&server)
+        .delete_message_batch(&params)
+        .await
+        .unwrap();
+
+    assert_eq!(deleted.id, "msgbatch_01");
+    assert_eq!(deleted.obj_type, "message_batch_deleted");
+}
+
+#[tokio::test]
+async fn wait_for_batch_reports_progress_and_survives_a_failed_poll() {
+    let server = MockServer::start().await;
+    let batch_path = "/messages/batches/msgbatch_01";
+    Mock::given(method("GET"))
+        .and(path(batch_path))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(message_batch("msgbatch_01", "in_progress")),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(batch_path))
+        .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "api_error", "message": "Internal server error"}
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    let mut ended = message_batch("msgbatch_01", "ended");
+    ended["request_counts"]["processing"] = 0.into();
+    ended["request_counts"]["succeeded"] = 1.into();
+    Mock::given(method("GET"))
+        .and(path(batch_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(ended))
+        .mount(&server)
+        .await;
+
+    let mut progress = Vec::new();
+    let batch = client::<MessageBatchError>(&server)
+        .wait_for_batch_with_progress(
+            "msgbatch_01",
+            PollConfig::new().with_interval(Duration::from_millis(10)),
+            &mut |counts| progress.push((counts.succeeded, counts.total())),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(batch.processing_status, ProcessingStatus::Ended);
+    assert_eq!(progress, [(0, 1), (1, 1)]);
+}
+
+#[tokio::test]
+async fn download_batch_results_to_copies_the_raw_body() {
+    let server = MockServer::start().await;
+    let results = concat!(
+        r#"{"custom_id":"a","result":{"type":"canceled"}}"#,
+        "\n",
+        r#"{"custom_id":"b","result":{"type":"expired"}}"#,
+        "\n",
+    );
+    let mut batch = message_batch("msgbatch_01", "ended");
+    batch["results_url"] = format!("{}/messages/batches/msgbatch_01/results", server.uri()).into();
+    authenticated(Mock::given(method("GET")).and(path("/messages/batches/msgbatch_01")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch))
+        .expect(1)
+        .mount(&server)
+        .await;
+    authenticated(Mock::given(method("GET")).and(path("/messages/batches/msgbatch_01/results")))
+        .respond_with(ResponseTemplate::new(200).set_body_string(results))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Through a trait object, which the writer parameter keeps possible
+    let batches: Box<dyn MessageBatchClient + Send + Sync> =
+        Box::new(client::<MessageBatchError>(&server));
+    let mut output = Vec::new();
+    let written = batches
+        .download_batch_results_to("msgbatch_01", &mut output)
+        .await
+        .unwrap();
+
+    assert_eq!(written, results.len() as u64);
+    assert_eq!(output, results.as_bytes());
+}
+
+#[tokio::test]
+async fn batch_results_split_across_chunks_are_reassembled() {
+    // Served by hand, as wiremock sends each body in one chunk. Lines are cut
+    // at arbitrary points, with a blank line and no final newline
+    let chunks: [&[u8]; 5] = [
+        br#"{"custom_id":"a","res"#,
+        br#"ult":{"type":"canceled"}}"#,
+        b"\n\n{\"custom_id\":\"b\",",
+        br#""result":{"type":"expired"}}"#,
+        b"\n{\"custom_id\":\"c\",\"result\":{\"type\":\"canceled\"}}",
+    ];
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n")
+            .await
+            .unwrap();
+        for chunk in chunks {
+            let mut frame = format!("{:x}\r\n", chunk.len()).into_bytes();
+            frame.extend_from_slice(chunk);
+            frame.extend_from_slice(b"\r\n");
+            socket.write_all(&frame).await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        socket.write_all(b"0\r\n\r\n").await.unwrap();
+    });
+
+    let server = MockServer::start().await;
+    let mut batch = message_batch("msgbatch_01", "ended");
+    batch["results_url"] = format!("{base_url}/messages/batches/msgbatch_01/results").into();
+    authenticated(Mock::given(method("GET")).and(path("/messages/batches/msgbatch_01")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(batch))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results: Vec<_> = client::<MessageBatchError>(&server)
+        .stream_message_batch_results("msgbatch_01")
+        .await
+        .unwrap()
+        .map(|result| result.unwrap().custom_id)
+        .collect()
+        .await;
+
+    assert_eq!(results, ["a", "b", "c"]);
+}
+
+/// Serves `body` to every request on keep-alive connections
+///
+/// Returns the base URL, the number of accepted connections and a channel
+/// receiving one message each time the client closes a connection. wiremock
+/// does not expose its connections, hence the hand-rolled server.
+async fn keep_alive_server(
+    body: &'static str,
+) -> (String, Arc<AtomicUsize>, mpsc::UnboundedReceiver<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let (closed_tx, closed_rx) = mpsc::unbounded_channel();
+    let counter = accepted.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let closed_tx = closed_tx.clone();
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    if n == 0 {
+                        let _ = closed_tx.send(());
+                        return;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                    // The client only sends GET requests, which end with the head
+                    if request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        request.clear();
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        socket.write_all(response.as_bytes()).await.unwrap();
+                    }
+                }
+            });
+        }
+    });
+    (base_url, accepted, closed_rx)
+}
+
+#[tokio::test]
+async fn shutdown_closes_idle_connections() {
+    let (base_url, accepted, mut closed) = keep_alive_server(
+        r#"{"type": "model", "id": "claude-3-5-sonnet-20240620", "display_name": "Claude 3.5 Sonnet", "created_at": "2024-06-20T00:00:00Z"}"#,
+    )
+    .await;
+    let build = || {
+        AnthropicClient::builder(API_KEY, API_VERSION)
+            .with_api_base_url(&base_url)
+            .build::<ModelError>()
+            .unwrap()
+    };
+
+    let client = build();
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+    assert_eq!(
+        accepted.load(Ordering::SeqCst),
+        1,
+        "keep-alive connection was not reused"
+    );
+
+    client.shutdown();
+    tokio::time::timeout(Duration::from_secs(5), closed.recv())
+        .await
+        .expect("idle connection still open after shutdown")
+        .unwrap();
+
+    build()
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+    assert_eq!(accepted.load(Ordering::SeqCst), 2);
+}
+
+fn rfc3339(time: &str) -> OffsetDateTime {
+    OffsetDateTime::parse(time, &Rfc3339).unwrap()
+}
+
+fn usage_bucket(starting_at: &str, ending_at: &str, output_tokens: u64) -> Value {
+    json!({
+        "starting_at": starting_at,
+        "ending_at": ending_at,
+        "results": [{"uncached_input_tokens": 100, "output_tokens": output_tokens}]
+    })
+}
+
+#[tokio::test]
+async fn usage_report_pages_are_followed() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/organizations/usage_report/messages")))
+        .and(query_param("starting_at", "2025-01-01T00:00:00Z"))
+        .and(query_param_is_missing("page"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [usage_bucket("2025-01-01T00:00:00Z", "2025-01-02T00:00:00Z", 10)],
+            "has_more": true,
+            "next_page": "page_2"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    authenticated(Mock::given(method("GET")).and(path("/organizations/usage_report/messages")))
+        .and(query_param("starting_at", "2025-01-01T00:00:00Z"))
+        .and(query_param("page", "page_2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [usage_bucket("2025-01-02T00:00:00Z", "2025-01-03T00:00:00Z", 20)],
+            "has_more": false,
+            "next_page": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client::<AdminError>(&server);
+    let params = UsageReportParams::new(rfc3339("2025-01-01T00:00:00Z"));
+    let buckets: Vec<_> = client
+        .get_usage_report_paginated(&params)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let output_tokens: Vec<_> = buckets.iter().map(|b| b.results[0].output_tokens).collect();
+    assert_eq!(output_tokens, [10, 20]);
+    assert_eq!(buckets[1].starting_at, rfc3339("2025-01-02T00:00:00Z"));
+}
+
+#[tokio::test]
+async fn usage_report_repeats_group_by() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/organizations/usage_report/messages")))
+        .and(query_param("group_by[]", "model"))
+        .and(query_param("group_by[]", "workspace_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{
+                "starting_at": "2025-01-01T00:00:00Z",
+                "ending_at": "2025-01-02T00:00:00Z",
+                "results": [{
+                    "model": "claude-3-5-sonnet-20240620",
+                    "workspace_id": "wrkspc_01",
+                    "api_key_id": null,
+                    "uncached_input_tokens": 100,
+                    "output_tokens": 10
+                }]
+            }],
+            "has_more": false,
+            "next_page": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let params = UsageReportParams::new(rfc3339("2025-01-01T00:00:00Z"))
+        .group_by(UsageGroupBy::Model)
+        .group_by(UsageGroupBy::WorkspaceId);
+    let report = client::<AdminError>(&server)
+        .get_usage_report(&params)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let group_by: Vec<_> = requests[0]
+        .url
+        .query_pairs()
+        .filter(|(key, _)| key == "group_by[]")
+        .map(|(_, value)| value.into_owned())
+        .collect();
+    assert_eq!(group_by, ["model", "workspace_id"]);
+
+    let result = &report.data[0].results[0];
+    assert_eq!(result.model.as_deref(), Some("claude-3-5-sonnet-20240620"));
+    assert_eq!(result.workspace_id.as_deref(), Some("wrkspc_01"));
+    assert_eq!(result.api_key_id, None);
+    assert_eq!(result.output_tokens, 10);
+}
+
+#[tokio::test]
+async fn admin_requests_take_request_options() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/organizations/workspaces")))
+        .and(header("x-tenant-id", "tenant-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [],
+            "first_id": null,
+            "has_more": false,
+            "last_id": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = RequestOptions::new().with_header(
+        HeaderName::from_static("x-tenant-id"),
+        HeaderValue::from_static("tenant-1"),
+    );
+    let workspaces = client::<AdminError>(&server)
+        .list_workspaces_with_options(None, &options)
+        .await
+        .unwrap();
+
+    assert!(workspaces.data.is_empty());
+}
+
+#[tokio::test]
+async fn user_agent_defaults_to_the_crate_and_can_be_overridden() {
+    let server = MockServer::start().await;
+    let user_agent = concat!("anthropic-ai-sdk-", env!("CARGO_PKG_VERSION"));
+    Mock::given(method("GET"))
+        .and(path("/models/default"))
+        .and(header("user-agent", user_agent))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "default",
+            "display_name": "Default",
+            "created_at": "2024-06-20T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models/custom"))
+        .and(header("user-agent", "my-pipeline/1.2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "custom",
+            "display_name": "Custom",
+            "created_at": "2024-06-20T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client::<ModelError>(&server)
+        .get_model("default")
+        .await
+        .unwrap();
+    AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_user_agent("my-pipeline/1.2")
+        .build::<ModelError>()
+        .unwrap()
+        .get_model("custom")
+        .await
+        .unwrap();
+}
+
+fn model(id: &str) -> Value {
+    json!({
+        "type": "model",
+        "id": id,
+        "display_name": "Claude 3.5 Sonnet",
+        "created_at": "2024-06-20T00:00:00Z"
+    })
+}
+
+#[tokio::test]
+async fn request_options_override_client_headers() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .and(header("x-tenant-id", "request"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_default_header(
+            HeaderName::from_static("x-tenant-id"),
+            HeaderValue::from_static("client"),
+        )
+        .build::<ModelError>()
+        .unwrap();
+    let options = RequestOptions::new().with_header(
+        HeaderName::from_static("x-tenant-id"),
+        HeaderValue::from_static("request"),
+    );
+    client
+        .get_model_with_options("claude-3-5-sonnet-20240620", &options)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].headers.get_all("x-tenant-id").iter().count(), 1);
+}
+
+#[tokio::test]
+async fn client_and_request_betas_share_one_header() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_beta("token-efficient-tools-2025-02-19")
+        .with_beta("output-128k-2025-02-19")
+        .build::<ModelError>()
+        .unwrap();
+    let options = RequestOptions::new().with_beta("files-api-2025-04-14");
+    client
+        .get_model_with_options("claude-3-5-sonnet-20240620", &options)
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let betas: Vec<_> = requests[0]
+        .headers
+        .get_all("anthropic-beta")
+        .iter()
+        .collect();
+    assert_eq!(
+        betas,
+        ["token-efficient-tools-2025-02-19,output-128k-2025-02-19,files-api-2025-04-14"]
+    );
+}
+
+#[tokio::test]
+async fn request_options_timeout_is_shorter_than_the_client_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(model("claude-3-5-sonnet-20240620"))
+                .set_delay(Duration::from_secs(2)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_timeout(Duration::from_secs(30))
+        .build::<ModelError>()
+        .unwrap();
+    let options = RequestOptions::new().with_timeout(Duration::from_millis(100));
+    let error = client
+        .get_model_with_options("claude-3-5-sonnet-20240620", &options)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, ModelError::Http(e) if e.is_timeout()),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn client_bound_to_a_local_address_reaches_the_server() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_local_address(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .build::<ModelError>()
+        .unwrap();
+    let model = client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    assert_eq!(model.id, "claude-3-5-sonnet-20240620");
+}
+
+#[tokio::test]
+async fn custom_http_client_still_sends_sdk_headers() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models")))
+        .and(header("anthropic-beta", "files-api-2025-04-14"))
+        .and(header("x-custom", "value"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [],
+            "has_more": false,
+            "first_id": null,
+            "last_id": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_http_client(reqwest::Client::new())
+        .with_beta(FILES_API_BETA)
+        .with_default_header(
+            reqwest::header::HeaderName::from_static("x-custom"),
+            reqwest::header::HeaderValue::from_static("value"),
+        )
+        .build::<ModelError>()
+        .unwrap();
+    client.list_models(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn version_header_can_be_left_to_a_gateway() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .and(header("x-api-key", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "claude-3-5-sonnet-20240620",
+            "display_name": "Claude 3.5 Sonnet",
+            "created_at": "2024-06-20T00:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .without_version_header()
+        .build::<ModelError>()
+        .unwrap()
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    assert!(!requests[0].headers.contains_key("anthropic-version"));
+}
+
+#[tokio::test]
+async fn upload_file_sends_multipart_form() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/files")))
+        .and(header("anthropic-beta", FILES_API_BETA))
+        .and(body_string_contains(r#"name="file"; filename="notes.txt""#))
+        .and(body_string_contains("hello files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "file_01",
+            "type": "file",
+            "filename": "notes.txt",
+            "mime_type": "text/plain",
+            "size_bytes": 11,
+            "created_at": "2025-04-14T12:00:00Z"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = client::<FileError>(&server)
+        .upload_file("hello files".into(), "notes.txt", "text/plain")
+        .await
+        .unwrap();
+
+    assert_eq!(file.id, "file_01");
+    assert_eq!(file.size_bytes, 11);
+    assert!(!file.downloadable);
+}
+
+#[tokio::test]
+async fn download_file_returns_raw_content() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/files/file_01/content")))
+        .and(header("anthropic-beta", FILES_API_BETA))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"\x00\x01binary".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/files/file_02/content"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "type": "error",
+            "error": { "type": "not_found_error", "message": "File not found" }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client::<FileError>(&server);
+    let content = client.download_file("file_01").await.unwrap();
+    assert_eq!(&content[..], b"\x00\x01binary");
+
+    let error = client.download_file("file_02").await.unwrap_err();
+    assert!(matches!(error, FileError::NotFound(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn retries_resend_the_idempotency_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "model",
+            "id": "claude-3-5-sonnet-20240620",
+            "display_name": "Claude 3.5 Sonnet",
+            "created_at": "2024-06-20T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_max_retries(1)
+        .with_max_retry_delay(Duration::from_millis(10))
+        .with_idempotency_keys(true)
+        .build::<ModelError>()
+        .unwrap();
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    let keys: Vec<_> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| request.headers[AnthropicClient::IDEMPOTENCY_KEY_HEADER].clone())
+        .collect();
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[0], keys[1]);
+    assert_ne!(keys[1], keys[2]);
+}
+
+/// `tracing` writer appending everything logged to a shared buffer
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn api_key_never_appears_in_logs() {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    authenticated(Mock::given(method("GET")))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_max_retries(1)
+        .with_max_retry_delay(Duration::from_millis(10))
+        .build::<ModelError>()
+        .unwrap();
+    tracing::debug!(?client, "client built");
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("retrying"), "{logs}");
+    assert!(logs.contains("attempts=2"), "{logs}");
+    assert!(!logs.contains(API_KEY), "{logs}");
+}
+
+#[tokio::test]
+async fn inspectors_see_every_attempt() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/models/claude-3-5-sonnet-20240620"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("request-id", "req_01")
+                .set_body_json(json!({
+                    "type": "model",
+                    "id": "claude-3-5-sonnet-20240620",
+                    "display_name": "Claude 3.5 Sonnet",
+                    "created_at": "2024-06-20T00:00:00Z"
+                })),
+        )
+        .mount(&server)
+        .await;
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let responses = Arc::new(Mutex::new(Vec::new()));
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_max_retries(1)
+        .with_max_retry_delay(Duration::from_millis(10))
+        .with_request_inspector({
+            let requests = requests.clone();
+            move |request| {
+                assert_eq!(request.headers()["x-api-key"], API_KEY);
+                requests
+                    .lock()
+                    .unwrap()
+                    .push(request.url().path().to_string());
+            }
+        })
+        .with_response_inspector({
+            let responses = responses.clone();
+            move |status, headers| {
+                responses
+                    .lock()
+                    .unwrap()
+                    .push((status.as_u16(), headers.get("request-id").cloned()));
+            }
+        })
+        .build::<ModelError>()
+        .unwrap();
+    client
+        .get_model("claude-3-5-sonnet-20240620")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *requests.lock().unwrap(),
+        ["/models/claude-3-5-sonnet-20240620"; 2]
+    );
+    assert_eq!(
+        *responses.lock().unwrap(),
+        [(529, None), (200, Some("req_01".parse().unwrap()))]
+    );
+}
+
+#[tokio::test]
+async fn streaming_requests_are_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    authenticated(Mock::given(method("POST")).and(path("/messages")))
+        .and(body_partial_json(json!({"stream": true})))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            "event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n",
+            "text/event-stream",
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::builder(API_KEY, API_VERSION)
+        .with_api_base_url(server.uri())
+        .with_max_retries(1)
+        .with_max_retry_delay(Duration::from_millis(10))
+        .build::<MessageError>()
+        .unwrap();
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    })
+    .with_stream(true);
+    let events: Vec<_> = client
+        .create_message_streaming(&params)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(
+        matches!(events[..], [Ok(StreamEvent::MessageStop)]),
+        "{events:?}"
+    );
+}
+
+#[tokio::test]
+async fn stream_error_events_are_typed_errors() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/messages")))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            concat!(
+                "event: message_start\n",
+                "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"type\":\"message\",\"role\":\"assistant\",\"content\":[],\"model\":\"claude-3-5-sonnet-20240620\",\"stop_reason\":null,\"stop_sequence\":null,\"usage\":{\"input_tokens\":10,\"output_tokens\":1}}}\n\n",
+                "event: error\n",
+                "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+            ),
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    })
+    .with_stream(true);
+    let events: Vec<_> = client::<MessageError>(&server)
+        .create_message_streaming(&params)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert!(
+        matches!(events[0], Ok(StreamEvent::MessageStart { .. })),
+        "{events:?}"
+    );
+    let error = events[1].as_ref().unwrap_err();
+    assert!(
+        matches!(error, MessageError::Overloaded(message) if message == "Overloaded"),
+        "{error:?}"
+    );
+    assert!(error.is_retryable());
+    assert_eq!(events.len(), 2);
+}
+
+#[tokio::test]
+async fn parse_sse_stream_reads_a_raw_response() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/stream"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            concat!(
+                "event: message_start\n",
+                "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"type\":\"message\",\"role\":\"assistant\",\"content\":[],\"model\":\"claude-3-5-sonnet-20240620\",\"stop_reason\":null,\"stop_sequence\":null,\"usage\":{\"input_tokens\":10,\"output_tokens\":1}}}\n\n",
+                "event: ping\n",
+                "data: {\"type\":\"ping\"}\n\n",
+                "event: content_block_delta\n",
+                "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Grüße\"}}\n\n",
+                "event: message_stop\n",
+                "data: {\"type\":\"message_stop\"}\n\n",
+                "data: [DONE]\n\n",
+                "data: not an event\n\n",
+            ),
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let response = reqwest::get(format!("{}/stream", server.uri()))
+        .await
+        .unwrap();
+    let events: Vec<_> = parse_sse_stream(response).collect().await;
+
+    assert_eq!(events.len(), 3, "{events:?}");
+    assert!(matches!(events[0], Ok(StreamEvent::MessageStart { .. })));
+    assert!(
+        matches!(
+            &events[1],
+            Ok(StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: ContentBlockDelta::TextDelta { text },
+            }) if text == "Grüße"
+        ),
+        "{events:?}"
+    );
+    assert!(matches!(events[2], Ok(StreamEvent::MessageStop)));
+}
+
+#[tokio::test]
+async fn message_stream_collects_the_text() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("POST")).and(path("/messages")))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            concat!(
+                "event: message_start\n",
+                "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"type\":\"message\",\"role\":\"assistant\",\"content\":[],\"model\":\"claude-3-5-sonnet-20240620\",\"stop_reason\":null,\"stop_sequence\":null,\"usage\":{\"input_tokens\":10,\"output_tokens\":1}}}\n\n",
+                "event: content_block_start\n",
+                "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+                "event: content_block_delta\n",
+                "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello\"}}\n\n",
+                "event: content_block_delta\n",
+                "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\", world\"}}\n\n",
+                "event: content_block_stop\n",
+                "data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+                "event: message_stop\n",
+                "data: {\"type\":\"message_stop\"}\n\n",
+            ),
+            "text/event-stream",
+        ))
+        .mount(&server)
+        .await;
+
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet-20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    })
+    .with_stream(true);
+    let text = client::<MessageError>(&server)
+        .create_message_streaming(&params)
+        .await
+        .unwrap()
+        .collect_text()
+        .await
+        .unwrap();
+
+    assert_eq!(text, "Hello, world");
+}
+
+#[tokio::test]
+async fn bedrock_addresses_the_model_in_the_path() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/model/anthropic.claude-3-5-sonnet-20240620-v1%3A0/invoke",
+        ))
+        .and(header("authorization", "signed"))
+        .and(body_json(json!({
+            "anthropic_version": "bedrock-2023-05-31",
+            "messages": [{"role": "user", "content": "Hello, Claude"}],
+            "max_tokens": 1024
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_bdrk_01",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-3-5-sonnet-20240620",
+            "content": [{"type": "text", "text": "Hello!"}],
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 2}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::bedrock("us-east-1")
+        .with_api_base_url(server.uri())
+        .with_auth_header_fn(|request| {
+            assert!(request.headers().get("x-api-key").is_none());
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert("authorization", "signed".parse().unwrap());
+            Ok(headers)
+        })
+        .build::<MessageError>()
+        .unwrap();
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "anthropic.claude-3-5-sonnet-20240620-v1:0".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let response = client.create_message(Some(&params)).await.unwrap();
+
+    assert_eq!(response.text(), "Hello!");
+}
+
+#[tokio::test]
+async fn vertex_addresses_the_model_in_the_path() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/projects/my-project/locations/us-east5/publishers/anthropic/models/claude-3-5-sonnet@20240620:rawPredict",
+        ))
+        .and(body_json(json!({
+            "anthropic_version": "vertex-2023-10-16",
+            "messages": [{"role": "user", "content": "Hello, Claude"}],
+            "max_tokens": 1024
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "msg_vrtx_01",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-3-5-sonnet-20240620",
+            "content": [{"type": "text", "text": "Hello!"}],
+            "stop_reason": "end_turn",
+            "stop_sequence": null,
+            "usage": {"input_tokens": 10, "output_tokens": 2}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::vertex("my-project", "us-east5")
+        .with_api_base_url(server.uri())
+        .build::<MessageError>()
+        .unwrap();
+    let params = CreateMessageParams::new(RequiredMessageParams {
+        model: "claude-3-5-sonnet@20240620".to_string(),
+        messages: vec![Message::user("Hello, Claude")],
+        max_tokens: 1024,
+    });
+    let response = client.create_message(Some(&params)).await.unwrap();
+
+    assert_eq!(response.id, "msg_vrtx_01");
+}
+
+#[tokio::test]
+async fn concurrent_messages_keep_input_order() {
+    let server = MockServer::start().await;
+    for (prompt, delay_ms) in [("first", 300), ("second", 0), ("third", 100)] {
+        Mock::given(method("POST"))
+            .and(path("/messages"))
+            .and(body_partial_json(json!({
+                "messages": [{"role": "user", "content": prompt}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_delay(Duration::from_millis(delay_ms))
+                    .set_body_json(json!({
+                        "id": format!("msg_{prompt}"),
+                        "type": "message",
+                        "role": "assistant",
+                        "model": "claude-3-5-sonnet-20240620",
+                        "content": [{"type": "text", "text": prompt}],
+                        "stop_reason": "end_turn",
+                        "stop_sequence": null,
+                        "usage": {"input_tokens": 10, "output_tokens": 1}
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client::<MessageError>(&server);
+    let params = ["first", "second", "third"].map(|prompt| {
+        CreateMessageParams::new(RequiredMessageParams {
+            model: "claude-3-5-sonnet-20240620".to_string(),
+            messages: vec![Message::user(prompt)],
+            max_tokens: 16,
+        })
+    });
+    let ids: Vec<String> = client
+        .create_messages_concurrent(params, 3)
+        .map(|result| result.unwrap().id)
+        .collect()
+        .await;
+
+    assert_eq!(ids, ["msg_first", "msg_second", "msg_third"]);
+}
+
+#[cfg(feature = "blocking")]
+#[tokio::test]
+async fn blocking_client_works_from_a_plain_thread_and_inside_a_runtime() {
+    let server = MockServer::start().await;
+    authenticated(Mock::given(method("GET")).and(path("/models/claude-3-5-sonnet-20240620")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(model("claude-3-5-sonnet-20240620")))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let async_client = client::<ModelError>(&server);
+    let blocking =
+        anthropic_ai_sdk::blocking::AnthropicClient::from_async::<ModelError>(async_client)
+            .unwrap();
+    let thread_client = blocking.clone();
+    let model = std::thread::spawn(move || thread_client.get_model("claude-3-5-sonnet-20240620"))
+        .join()
+        .unwrap()
+        .unwrap();
+    assert_eq!(model.id, "claude-3-5-sonnet-20240620");
+
+    // Blocks this executor thread, but neither the call nor the drop panics
+    let model = blocking.get_model("claude-3-5-sonnet-20240620").unwrap();
+    assert_eq!(model.id, "claude-3-5-sonnet-20240620");
+    drop(blocking);
+}
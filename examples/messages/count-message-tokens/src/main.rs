@@ -28,7 +28,9 @@ async fn main() {
     let body = CountMessageTokensParams {
         model: "claude-3-5-sonnet-20240620".to_string(),
         messages: vec![Message::new_text(Role::User, "Hello, Claude")],
-    };
+        ..Default::default()
+    }
+    .with_system("You are a helpful assistant.");
 
     info!("body: {:?}", body);
 
@@ -1,7 +1,4 @@
-use anthropic_ai_sdk::client::AnthropicClient;
-use anthropic_ai_sdk::types::message::{
-    CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role,
-};
+use anthropic_ai_sdk::prelude::*;
 use std::env;
 use tracing::{error, info};
 
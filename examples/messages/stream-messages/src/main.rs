@@ -1,6 +1,5 @@
 use anthropic_ai_sdk::client::AnthropicClient;
 use anthropic_ai_sdk::types::message::{CreateMessageParams, Message, MessageClient, MessageError, RequiredMessageParams, Role, Thinking, ThinkingType};
-use futures_util::StreamExt;
 use std::env;
 use tracing::{error, info};
 